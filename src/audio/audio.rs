@@ -0,0 +1,573 @@
+use std::collections::HashMap;
+use std::io;
+
+use bevy::audio::{DefaultSpatialScale, SpatialScale, Volume};
+use bevy::prelude::{
+    resource_changed, App, AssetServer, AudioBundle, AudioSink, AudioSinkPlayback,
+    AudioSource, BuildChildren, Commands, Component, DespawnRecursiveExt, Entity,
+    Event, EventReader, EventWriter, FromWorld, GlobalTransform, Handle, info,
+    IntoSystemConfigs, OnEnter, OnExit, Plugin, PlaybackSettings, Query, Real, Res, ResMut,
+    Resource, SpatialAudioSink, SpatialListener, State, state_changed, Time, Transform,
+    TransformBundle, Update, Vec3, With, Without, World,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::game::game::GameState;
+use crate::game::location::location::{CurrentLocation, LocationRegistry};
+use crate::game::movement::movement::MoveVelocity;
+use crate::settings::settings::{load_settings, save_settings};
+use crate::AppState;
+
+const MUSIC_FADE_SECONDS: f32 = 2.0;
+/// Below this speed an entity counts as standing still and its footsteps stop.
+const FOOTSTEPS_MIN_SPEED: f32 = 20.0;
+
+pub struct PepaAudioPlugin;
+
+impl Plugin for PepaAudioPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(AudioVolumes::load())
+            .init_resource::<SfxLibrary>()
+            .init_resource::<CurrentMusic>()
+            .init_resource::<SpatialAudioSettings>()
+            .add_event::<PlaySfxEvent>()
+            .add_event::<ChangeMusicEvent>()
+            .add_systems(OnEnter(GameState::Paused), pause_gameplay_audio)
+            .add_systems(OnExit(GameState::Paused), resume_gameplay_audio)
+            .add_systems(
+                Update,
+                (
+                    select_state_music.run_if(state_changed::<AppState>),
+                    select_location_music.run_if(state_changed::<CurrentLocation>),
+                    change_music,
+                    fade_music,
+                    play_footsteps,
+                    play_sfx,
+                    apply_volumes.run_if(resource_changed::<AudioVolumes>),
+                    apply_spatial_settings.run_if(resource_changed::<SpatialAudioSettings>),
+                )
+                    .chain(),
+            );
+    }
+}
+
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum AudioChannel {
+    Master,
+    Music,
+    Sfx,
+    Ui,
+}
+
+impl AudioChannel {
+    pub const CHANNELS: [Self; 4] = [
+        AudioChannel::Master,
+        AudioChannel::Music,
+        AudioChannel::Sfx,
+        AudioChannel::Ui,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            AudioChannel::Master => "Master",
+            AudioChannel::Music => "Music",
+            AudioChannel::Sfx => "Effects",
+            AudioChannel::Ui => "Interface",
+        }
+    }
+}
+
+/// Volume of every channel, each one is scaled by the master volume.
+/// Persisted to `audio.ron` in the user config directory.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct AudioVolumes {
+    pub master: f32,
+    pub music: f32,
+    pub sfx: f32,
+    pub ui: f32,
+}
+
+impl Default for AudioVolumes {
+    fn default() -> Self {
+        Self {
+            master: 1.0,
+            music: 0.6,
+            sfx: 0.8,
+            ui: 0.8,
+        }
+    }
+}
+
+impl AudioVolumes {
+    const FILE_NAME: &'static str = "audio.ron";
+
+    pub fn load() -> Self {
+        load_settings(Self::FILE_NAME)
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        save_settings(Self::FILE_NAME, self)
+    }
+
+    pub fn channel(&self, channel: AudioChannel) -> f32 {
+        match channel {
+            AudioChannel::Master => self.master,
+            AudioChannel::Music => self.music,
+            AudioChannel::Sfx => self.sfx,
+            AudioChannel::Ui => self.ui,
+        }
+    }
+
+    pub fn set_channel(&mut self, channel: AudioChannel, volume: f32) {
+        let volume = volume.clamp(0.0, 1.0);
+
+        match channel {
+            AudioChannel::Master => self.master = volume,
+            AudioChannel::Music => self.music = volume,
+            AudioChannel::Sfx => self.sfx = volume,
+            AudioChannel::Ui => self.ui = volume,
+        }
+    }
+
+    /// Volume a sink of the channel should play at, master included.
+    pub fn effective(&self, channel: AudioChannel) -> f32 {
+        match channel {
+            AudioChannel::Master => self.master,
+            _ => self.master * self.channel(channel),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SfxId {
+    CoinPick,
+    Explosion,
+    Laser,
+    Gunshot,
+    Footstep,
+    UiClick,
+    UiBack,
+}
+
+impl SfxId {
+    pub const ALL: [Self; 7] = [
+        SfxId::CoinPick,
+        SfxId::Explosion,
+        SfxId::Laser,
+        SfxId::Gunshot,
+        SfxId::Footstep,
+        SfxId::UiClick,
+        SfxId::UiBack,
+    ];
+
+    pub fn path(&self) -> &'static str {
+        match self {
+            SfxId::CoinPick => "audio/coin_pick.wav",
+            SfxId::Explosion => "audio/explosionCrunch_000.ogg",
+            SfxId::Laser => "audio/laserLarge_000.ogg",
+            SfxId::Gunshot => "audio/annihilation-gun-sound.wav",
+            SfxId::Footstep => "audio/walk-sound.wav",
+            SfxId::UiClick => "audio/pluck_001.ogg",
+            SfxId::UiBack => "audio/pluck_002.ogg",
+        }
+    }
+
+    pub fn channel(&self) -> AudioChannel {
+        match self {
+            SfxId::UiClick | SfxId::UiBack => AudioChannel::Ui,
+            _ => AudioChannel::Sfx,
+        }
+    }
+}
+
+/// Sound effect handles loaded up front so requesting one never waits on the asset server.
+#[derive(Resource, Debug)]
+pub struct SfxLibrary {
+    pub sounds: HashMap<SfxId, Handle<AudioSource>>,
+}
+
+impl FromWorld for SfxLibrary {
+    fn from_world(world: &mut World) -> Self {
+        let asset_server = world.resource::<AssetServer>();
+
+        Self {
+            sounds: SfxId::ALL
+                .iter()
+                .map(|id| (*id, asset_server.load(id.path())))
+                .collect(),
+        }
+    }
+}
+
+/// How in-world sounds fade out with the distance to the [`SpatialListener`].
+#[derive(Resource, Debug, Clone, Copy, PartialEq)]
+pub struct SpatialAudioSettings {
+    /// Distance in pixels up to which a sound plays at full volume,
+    /// past it the volume falls off with the square of the distance.
+    pub falloff_distance: f32,
+    /// Distance between the listener's ears in pixels, wider means stronger panning.
+    pub ear_gap: f32,
+}
+
+impl Default for SpatialAudioSettings {
+    fn default() -> Self {
+        Self {
+            falloff_distance: 400.0,
+            ear_gap: 400.0,
+        }
+    }
+}
+
+impl SpatialAudioSettings {
+    pub fn listener(&self) -> SpatialListener {
+        SpatialListener::new(self.ear_gap)
+    }
+
+    /// Rodio attenuates past one unit of distance, so the falloff distance becomes that unit.
+    /// Depth is ignored as z only orders sprites.
+    pub fn spatial_scale(&self) -> SpatialScale {
+        SpatialScale::new_2d(1.0 / self.falloff_distance)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SfxSource {
+    /// Plays at the same volume wherever the listener is, e.g. UI sounds.
+    Global,
+    /// Plays from a fixed point in the world.
+    At(Vec3),
+    /// Follows the entity while it moves.
+    Entity(Entity),
+}
+
+#[derive(Event, Debug)]
+pub struct PlaySfxEvent {
+    pub id: SfxId,
+    pub source: SfxSource,
+}
+
+impl PlaySfxEvent {
+    pub fn new(id: SfxId) -> Self {
+        Self {
+            id,
+            source: SfxSource::Global,
+        }
+    }
+
+    pub fn at(id: SfxId, position: Vec3) -> Self {
+        Self {
+            id,
+            source: SfxSource::At(position),
+        }
+    }
+
+    pub fn from_entity(id: SfxId, entity: Entity) -> Self {
+        Self {
+            id,
+            source: SfxSource::Entity(entity),
+        }
+    }
+}
+
+/// Plays a looping footsteps sound from the entity while it moves.
+#[derive(Component, Debug, Default)]
+pub struct Footsteps {
+    pub sound: Option<Entity>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MusicId {
+    MainMenu,
+    Station,
+    Forest,
+}
+
+impl MusicId {
+    /// Short synthesized loops standing in until the real soundtrack is added.
+    pub fn path(&self) -> &'static str {
+        match self {
+            MusicId::MainMenu => "audio/music/placeholder-menu.wav",
+            MusicId::Station => "audio/music/placeholder-station.wav",
+            MusicId::Forest => "audio/music/placeholder-forest.wav",
+        }
+    }
+
+    /// Music outside the game, in the game every location picks its own.
+    pub fn for_state(app_state: &AppState) -> Option<MusicId> {
+        match app_state {
+            AppState::Logo | AppState::Loading | AppState::GameOver => None,
+            AppState::MainMenu => Some(MusicId::MainMenu),
+            AppState::Game => None,
+        }
+    }
+}
+
+/// Crossfades from the playing track to `track`, `None` fades the music out.
+#[derive(Event, Debug)]
+pub struct ChangeMusicEvent {
+    pub track: Option<MusicId>,
+}
+
+#[derive(Resource, Debug, Default)]
+pub struct CurrentMusic {
+    pub track: Option<MusicId>,
+}
+
+#[derive(Component, Debug)]
+pub struct MusicTrack {
+    /// Fade level from 0 to 1, multiplied with the music channel volume.
+    pub fade: f32,
+    pub fading_out: bool,
+}
+
+pub fn select_state_music(
+    app_state: Res<State<AppState>>,
+    mut change_music_writer: EventWriter<ChangeMusicEvent>,
+) {
+    // Entering the game also enters a location, which brings its own music.
+    if *app_state.get() == AppState::Game {
+        return;
+    }
+
+    change_music_writer.send(ChangeMusicEvent {
+        track: MusicId::for_state(app_state.get()),
+    });
+}
+
+/// Crossfades to the music of the location the player went into.
+pub fn select_location_music(
+    current_location: Res<State<CurrentLocation>>,
+    registry: Res<LocationRegistry>,
+    mut change_music_writer: EventWriter<ChangeMusicEvent>,
+) {
+    change_music_writer.send(ChangeMusicEvent {
+        track: registry
+            .get(current_location.get().0)
+            .and_then(|definition| definition.music),
+    });
+}
+
+pub fn change_music(
+    mut commands: Commands,
+    mut change_music_reader: EventReader<ChangeMusicEvent>,
+    mut current_music: ResMut<CurrentMusic>,
+    mut track_query: Query<&mut MusicTrack>,
+    asset_server: Res<AssetServer>,
+) {
+    for event in change_music_reader.read() {
+        if event.track == current_music.track {
+            continue;
+        }
+
+        info!("Changing music from {:?} to {:?}", current_music.track, event.track);
+        current_music.track = event.track;
+
+        for mut track in track_query.iter_mut() {
+            track.fading_out = true;
+        }
+
+        if let Some(music_id) = event.track {
+            commands.spawn((
+                AudioBundle {
+                    source: asset_server.load(music_id.path()),
+                    settings: PlaybackSettings::LOOP.with_volume(Volume::ZERO),
+                },
+                MusicTrack {
+                    fade: 0.0,
+                    fading_out: false,
+                },
+                AudioChannel::Music,
+            ));
+        }
+    }
+}
+
+pub fn fade_music(
+    mut commands: Commands,
+    mut track_query: Query<(Entity, &mut MusicTrack, Option<&AudioSink>)>,
+    volumes: Res<AudioVolumes>,
+    // Real time, so the music keeps fading while the game is paused.
+    time: Res<Time<Real>>,
+) {
+    let fade_step = time.delta_seconds() / MUSIC_FADE_SECONDS;
+
+    for (entity, mut track, sink) in track_query.iter_mut() {
+        if track.fading_out {
+            track.fade = (track.fade - fade_step).max(0.0);
+        } else {
+            track.fade = (track.fade + fade_step).min(1.0);
+        }
+
+        if track.fading_out && track.fade == 0.0 {
+            commands.entity(entity).despawn_recursive();
+            continue;
+        }
+
+        if let Some(sink) = sink {
+            sink.set_volume(volumes.effective(AudioChannel::Music) * track.fade);
+        }
+    }
+}
+
+pub fn play_sfx(
+    mut commands: Commands,
+    mut play_sfx_reader: EventReader<PlaySfxEvent>,
+    emitter_query: Query<(), With<GlobalTransform>>,
+    sfx_library: Res<SfxLibrary>,
+    volumes: Res<AudioVolumes>,
+) {
+    for event in play_sfx_reader.read() {
+        let Some(source) = sfx_library.sounds.get(&event.id) else {
+            continue;
+        };
+
+        let channel = event.id.channel();
+        let audio_bundle = AudioBundle {
+            source: source.clone(),
+            settings: PlaybackSettings::DESPAWN
+                .with_volume(Volume::new(volumes.effective(channel))),
+        };
+
+        match event.source {
+            SfxSource::Global => {
+                commands.spawn((audio_bundle, channel));
+            }
+            SfxSource::At(position) => {
+                commands.spawn((
+                    spatial(audio_bundle),
+                    TransformBundle::from_transform(Transform::from_translation(position)),
+                    channel,
+                ));
+            }
+            SfxSource::Entity(emitter) => {
+                // The emitter may be gone by the time its sound is requested.
+                if emitter_query.get(emitter).is_err() {
+                    continue;
+                }
+
+                commands.entity(emitter).with_children(|parent| {
+                    parent.spawn((spatial(audio_bundle), TransformBundle::default(), channel));
+                });
+            }
+        }
+    }
+}
+
+pub fn play_footsteps(
+    mut commands: Commands,
+    mut query: Query<(Entity, &MoveVelocity, &mut Footsteps)>,
+    sfx_library: Res<SfxLibrary>,
+    volumes: Res<AudioVolumes>,
+) {
+    for (entity, velocity, mut footsteps) in query.iter_mut() {
+        let is_moving = velocity.length() > FOOTSTEPS_MIN_SPEED;
+
+        match (is_moving, footsteps.sound) {
+            (true, None) => {
+                let Some(source) = sfx_library.sounds.get(&SfxId::Footstep) else {
+                    continue;
+                };
+
+                let mut sound = None;
+                commands.entity(entity).with_children(|parent| {
+                    sound = Some(
+                        parent
+                            .spawn((
+                                spatial(AudioBundle {
+                                    source: source.clone(),
+                                    settings: PlaybackSettings::LOOP.with_volume(Volume::new(
+                                        volumes.effective(AudioChannel::Sfx),
+                                    )),
+                                }),
+                                TransformBundle::default(),
+                                AudioChannel::Sfx,
+                            ))
+                            .id(),
+                    );
+                });
+                footsteps.sound = sound;
+            }
+            (false, Some(sound)) => {
+                if let Some(sound_commands) = commands.get_entity(sound) {
+                    sound_commands.despawn_recursive();
+                }
+                footsteps.sound = None;
+            }
+            _ => {}
+        }
+    }
+}
+
+fn spatial(audio_bundle: AudioBundle) -> AudioBundle {
+    AudioBundle {
+        settings: audio_bundle.settings.with_spatial(true),
+        ..audio_bundle
+    }
+}
+
+pub fn apply_volumes(
+    volumes: Res<AudioVolumes>,
+    sink_query: Query<(&AudioChannel, &AudioSink), Without<MusicTrack>>,
+    spatial_sink_query: Query<(&AudioChannel, &SpatialAudioSink)>,
+) {
+    for (channel, sink) in sink_query.iter() {
+        sink.set_volume(volumes.effective(*channel));
+    }
+
+    for (channel, sink) in spatial_sink_query.iter() {
+        sink.set_volume(volumes.effective(*channel));
+    }
+}
+
+pub fn apply_spatial_settings(
+    spatial_settings: Res<SpatialAudioSettings>,
+    mut default_spatial_scale: ResMut<DefaultSpatialScale>,
+    mut listener_query: Query<&mut SpatialListener>,
+) {
+    default_spatial_scale.0 = spatial_settings.spatial_scale();
+
+    for mut listener in listener_query.iter_mut() {
+        *listener = spatial_settings.listener();
+    }
+}
+
+/// Pauses every sound effect playing in the world while the game is paused,
+/// music and interface sounds keep playing.
+pub fn pause_gameplay_audio(
+    sink_query: Query<(&AudioChannel, &AudioSink)>,
+    spatial_sink_query: Query<(&AudioChannel, &SpatialAudioSink)>,
+) {
+    set_gameplay_audio_paused(&sink_query, &spatial_sink_query, true);
+}
+
+pub fn resume_gameplay_audio(
+    sink_query: Query<(&AudioChannel, &AudioSink)>,
+    spatial_sink_query: Query<(&AudioChannel, &SpatialAudioSink)>,
+) {
+    set_gameplay_audio_paused(&sink_query, &spatial_sink_query, false);
+}
+
+fn set_gameplay_audio_paused(
+    sink_query: &Query<(&AudioChannel, &AudioSink)>,
+    spatial_sink_query: &Query<(&AudioChannel, &SpatialAudioSink)>,
+    paused: bool,
+) {
+    for (channel, sink) in sink_query.iter() {
+        if *channel == AudioChannel::Sfx {
+            set_paused(sink, paused);
+        }
+    }
+    for (channel, sink) in spatial_sink_query.iter() {
+        if *channel == AudioChannel::Sfx {
+            set_paused(sink, paused);
+        }
+    }
+}
+
+fn set_paused(sink: &impl AudioSinkPlayback, paused: bool) {
+    if paused {
+        sink.pause();
+    } else {
+        sink.play();
+    }
+}
@@ -0,0 +1,255 @@
+use bevy::input::mouse::MouseWheel;
+use bevy::prelude::{
+    default, in_state, App, ButtonInput, Camera2dBundle, Commands, Component, Event, EventReader,
+    EventWriter, GamepadButton, GamepadButtonType, GlobalTransform, IntoSystemConfigs, KeyCode,
+    OrthographicProjection, Plugin, PostUpdate, Query, Res, Startup, Time, Timer, TimerMode,
+    Transform, TransformSystem, Update, Vec2, With, Without,
+};
+use rand::Rng;
+
+use crate::audio::audio::SpatialAudioSettings;
+use crate::game::collision::collision::{clamp_to_world_bounds, WorldBounds};
+use crate::game::game::GameState;
+use crate::game::health::health::{DamageEvent, Health};
+use crate::game::player::player::Player;
+use crate::parallax::parallax::scroll_parallax_layers;
+use crate::AppState;
+
+/// Targets further away than this are jumped to instead of followed, e.g. after
+/// the player went through a door.
+const CAMERA_SNAP_DISTANCE: f32 = 1000.0;
+const DEFAULT_SMOOTHING: f32 = 4.0;
+const DEFAULT_DEAD_ZONE: Vec2 = Vec2::new(64.0, 48.0);
+const DEFAULT_ZOOM_LEVELS: [f32; 4] = [0.5, 0.75, 1.0, 1.5];
+const DEFAULT_ZOOM_LEVEL: usize = 2;
+const DEFAULT_ZOOM_SPEED: f32 = 8.0;
+/// Shake of a hit taking the whole health away, smaller hits shake less.
+const HIT_SHAKE_INTENSITY: f32 = 40.0;
+const HIT_SHAKE_SECONDS: f32 = 0.3;
+
+pub const ZOOM_IN_KEY: KeyCode = KeyCode::Equal;
+pub const ZOOM_OUT_KEY: KeyCode = KeyCode::Minus;
+
+pub struct CameraPlugin;
+
+impl Plugin for CameraPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<ScreenShakeEvent>()
+            .add_systems(Startup, spawn_camera)
+            .add_systems(
+                Update,
+                (zoom_camera, shake_on_player_hit)
+                    .run_if(in_state(AppState::Game))
+                    .run_if(in_state(GameState::Running)),
+            )
+            .add_systems(
+                PostUpdate,
+                (start_screen_shake, follow_camera_target)
+                    .chain()
+                    .after(clamp_to_world_bounds)
+                    .before(scroll_parallax_layers)
+                    .before(TransformSystem::TransformPropagate)
+                    .run_if(in_state(AppState::Game)),
+            );
+    }
+}
+
+/// Camera of the game world: follows the [`CameraTarget`], stays inside the location's
+/// [`WorldBounds`] and shakes on [`ScreenShakeEvent`]s.
+#[derive(Component, Debug, Clone)]
+pub struct GameCamera {
+    /// How quickly the camera catches up with its target, per second.
+    pub smoothing: f32,
+    /// Half size of the box around the view center the target moves in freely.
+    pub dead_zone: Vec2,
+    /// Projection scales to switch between, from the closest to the farthest.
+    pub zoom_levels: Vec<f32>,
+    pub zoom_level: usize,
+    /// How quickly the projection scale approaches the chosen level, per second.
+    pub zoom_speed: f32,
+    /// Point the camera looks at, before the shake is added.
+    focus: Vec2,
+    shake_intensity: f32,
+    shake_timer: Timer,
+}
+
+impl Default for GameCamera {
+    fn default() -> Self {
+        Self {
+            smoothing: DEFAULT_SMOOTHING,
+            dead_zone: DEFAULT_DEAD_ZONE,
+            zoom_levels: DEFAULT_ZOOM_LEVELS.to_vec(),
+            zoom_level: DEFAULT_ZOOM_LEVEL,
+            zoom_speed: DEFAULT_ZOOM_SPEED,
+            focus: Vec2::ZERO,
+            shake_intensity: 0.0,
+            shake_timer: Timer::from_seconds(0.0, TimerMode::Once),
+        }
+    }
+}
+
+impl GameCamera {
+    pub fn zoom_scale(&self) -> f32 {
+        self.zoom_levels
+            .get(self.zoom_level)
+            .copied()
+            .unwrap_or(1.0)
+    }
+
+    fn shake_offset(&self) -> Vec2 {
+        if self.shake_timer.finished() {
+            return Vec2::ZERO;
+        }
+
+        // Fades out over the shake, so it ends without a jump.
+        let strength = self.shake_intensity * self.shake_timer.fraction_remaining();
+        let mut rng = rand::thread_rng();
+
+        Vec2::new(rng.gen_range(-1.0..1.0), rng.gen_range(-1.0..1.0)) * strength
+    }
+}
+
+/// Entity the camera follows.
+#[derive(Component, Debug)]
+pub struct CameraTarget;
+
+#[derive(Event, Debug)]
+pub struct ScreenShakeEvent {
+    /// Largest offset of the view in pixels, at the start of the shake.
+    pub intensity: f32,
+    pub seconds: f32,
+}
+
+impl ScreenShakeEvent {
+    pub fn new(intensity: f32, seconds: f32) -> Self {
+        Self { intensity, seconds }
+    }
+}
+
+pub fn spawn_camera(mut commands: Commands, spatial_audio_settings: Res<SpatialAudioSettings>) {
+    commands.spawn((
+        Camera2dBundle {
+            transform: Transform::from_xyz(0.0, 0.0, 1.0),
+            // The camera stays at the characters' depth, this lets foreground layers
+            // in front of them be seen as well.
+            projection: OrthographicProjection {
+                near: -1000.0,
+                ..default()
+            },
+            ..default()
+        },
+        GameCamera::default(),
+        spatial_audio_settings.listener(),
+    ));
+}
+
+/// `=` and `-`, the mouse wheel or the gamepad bumpers step through the zoom levels.
+pub fn zoom_camera(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    gamepad_buttons: Res<ButtonInput<GamepadButton>>,
+    mut mouse_wheel_reader: EventReader<MouseWheel>,
+    mut camera_query: Query<&mut GameCamera>,
+) {
+    let Ok(mut camera) = camera_query.get_single_mut() else {
+        return;
+    };
+
+    let wheel: f32 = mouse_wheel_reader.read().map(|event| event.y).sum();
+    let bumper_pressed = |button_type: GamepadButtonType| {
+        gamepad_buttons
+            .get_just_pressed()
+            .any(|button| button.button_type == button_type)
+    };
+
+    let zoom_in = keyboard_input.just_pressed(ZOOM_IN_KEY)
+        || bumper_pressed(GamepadButtonType::RightTrigger)
+        || wheel > 0.0;
+    let zoom_out = keyboard_input.just_pressed(ZOOM_OUT_KEY)
+        || bumper_pressed(GamepadButtonType::LeftTrigger)
+        || wheel < 0.0;
+
+    if zoom_in && camera.zoom_level > 0 {
+        camera.zoom_level -= 1;
+    } else if zoom_out && camera.zoom_level + 1 < camera.zoom_levels.len() {
+        camera.zoom_level += 1;
+    }
+}
+
+pub fn shake_on_player_hit(
+    mut damage_reader: EventReader<DamageEvent>,
+    mut shake_writer: EventWriter<ScreenShakeEvent>,
+    player_query: Query<&Health, With<Player>>,
+) {
+    for event in damage_reader.read() {
+        let Ok(health) = player_query.get(event.entity) else {
+            continue;
+        };
+
+        let share = (event.amount / health.max.max(1.0)).min(1.0);
+        shake_writer.send(ScreenShakeEvent::new(
+            HIT_SHAKE_INTENSITY * share.sqrt(),
+            HIT_SHAKE_SECONDS,
+        ));
+    }
+}
+
+/// A stronger shake replaces the running one, weaker ones are ignored while it lasts.
+pub fn start_screen_shake(
+    mut shake_reader: EventReader<ScreenShakeEvent>,
+    mut camera_query: Query<&mut GameCamera>,
+) {
+    let Ok(mut camera) = camera_query.get_single_mut() else {
+        return;
+    };
+
+    for event in shake_reader.read() {
+        let current = camera.shake_intensity * camera.shake_timer.fraction_remaining();
+        if event.intensity > current {
+            camera.shake_intensity = event.intensity;
+            camera.shake_timer = Timer::from_seconds(event.seconds, TimerMode::Once);
+        }
+    }
+}
+
+/// Runs after the bodies were moved and clamped, so the camera sees where the target
+/// ends up this frame.
+pub fn follow_camera_target(
+    time: Res<Time>,
+    mut camera_query: Query<(&mut GameCamera, &mut Transform, &mut OrthographicProjection)>,
+    target_query: Query<&Transform, (With<CameraTarget>, Without<GameCamera>)>,
+    bounds_query: Query<(&WorldBounds, &GlobalTransform)>,
+) {
+    let Ok((mut camera, mut transform, mut projection)) = camera_query.get_single_mut() else {
+        return;
+    };
+    let delta = time.delta_seconds();
+
+    let zoom_scale = camera.zoom_scale();
+    projection.scale += (zoom_scale - projection.scale) * (camera.zoom_speed * delta).min(1.0);
+
+    if let Ok(target_transform) = target_query.get_single() {
+        let target = target_transform.translation.truncate();
+        let offset = target - camera.focus;
+
+        if offset.length() > CAMERA_SNAP_DISTANCE {
+            camera.focus = target;
+        } else {
+            let outside_dead_zone = offset - offset.clamp(-camera.dead_zone, camera.dead_zone);
+            let step = (camera.smoothing * delta).min(1.0);
+            camera.focus += outside_dead_zone * step;
+        }
+    }
+
+    if let Ok((bounds, bounds_transform)) = bounds_query.get_single() {
+        let rect = bounds.to_world(bounds_transform);
+        let half_view = projection.area.size() / 2.0;
+        // A location smaller than the view is centered instead.
+        let min = (rect.min + half_view).min(rect.center());
+        let max = (rect.max - half_view).max(rect.center());
+        camera.focus = camera.focus.clamp(min, max);
+    }
+
+    camera.shake_timer.tick(time.delta());
+    let position = camera.focus + camera.shake_offset();
+    transform.translation = position.extend(transform.translation.z);
+}
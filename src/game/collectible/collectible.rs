@@ -0,0 +1,169 @@
+use std::f32::consts::TAU;
+
+use bevy::prelude::{
+    default, in_state, info, on_event, warn, Added, App, AssetServer, BuildChildren, Commands,
+    Component, DespawnRecursiveExt, EventReader, EventWriter, IntoSystemConfigs, Parent, Plugin,
+    Query, Res, ResMut, SpatialBundle, SpriteBundle, StateScoped, Time, Transform, Update, With,
+};
+use bevy_rapier2d::prelude::{
+    ActiveCollisionTypes, ActiveEvents, Collider, CollisionEvent, Sensor,
+};
+
+use crate::audio::audio::{PlaySfxEvent, SfxId};
+use crate::game::game::{GameState, WorldFlags};
+use crate::game::inventory::inventory::{Inventory, ItemId};
+use crate::game::location::location::{CurrentLocation, Location, LocationRegistry};
+use crate::game::player::player::Player;
+use crate::tilemap::tiled::{MapCollectible, MapObject};
+use crate::ysort::ysort::YSort;
+use crate::AppState;
+
+const PICKUP_RADIUS: f32 = 16.0;
+const BOB_HEIGHT: f32 = 4.0;
+/// Bobs per second.
+const BOB_SPEED: f32 = 0.8;
+/// Turns per second.
+const SPIN_SPEED: f32 = 0.5;
+
+pub struct CollectiblePlugin;
+
+impl Plugin for CollectiblePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, spawn_collectibles.run_if(in_state(AppState::Game)))
+            .add_systems(
+                Update,
+                (
+                    animate_collectibles,
+                    pick_up_collectibles.run_if(on_event::<CollisionEvent>()),
+                )
+                    .run_if(in_state(GameState::Running)),
+            );
+    }
+}
+
+/// Item lying in the world, goes into the inventory of the player touching it.
+#[derive(Component, Debug, Clone)]
+pub struct Collectible {
+    pub item: ItemId,
+    pub count: u32,
+    /// [`WorldFlags`] flag set once it is picked up, so it does not come back with the location.
+    pub flag: String,
+}
+
+/// Sprite of a collectible, bobs and spins around the collectible's sensor.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct CollectibleSprite {
+    /// Offsets the animation, so collectibles next to each other do not move in step.
+    pub phase: f32,
+}
+
+/// Spawns the collectibles placed on a map, except the ones already picked up.
+pub fn spawn_collectibles(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    registry: Res<LocationRegistry>,
+    world_flags: Res<WorldFlags>,
+    collectible_query: Query<(&MapCollectible, &MapObject, &Parent), Added<MapCollectible>>,
+    map_query: Query<(&Transform, &Location)>,
+) {
+    for (map_collectible, object, parent) in collectible_query.iter() {
+        let Ok((map_transform, location)) = map_query.get(parent.get()) else {
+            continue;
+        };
+        let Some(item) = ItemId::from_name(&map_collectible.item) else {
+            warn!(
+                "Unknown item {} in collectible {}",
+                map_collectible.item, object.name
+            );
+            continue;
+        };
+        let Some(definition) = registry.get(location.id) else {
+            continue;
+        };
+
+        let flag = format!("collected:{}:{}", definition.name, object.name);
+        if world_flags.is_set(&flag) {
+            continue;
+        }
+
+        let position = map_transform
+            .transform_point(object.position.extend(0.0))
+            .truncate();
+        let phase = position.x * 0.01;
+
+        commands
+            .spawn((
+                SpatialBundle::from_transform(Transform::from_translation(position.extend(0.0))),
+                Collider::ball(PICKUP_RADIUS),
+                Sensor,
+                // The player is kinematic and map geometry is fixed, so touching it would
+                // go unnoticed by default.
+                ActiveCollisionTypes::default() | ActiveCollisionTypes::KINEMATIC_STATIC,
+                ActiveEvents::COLLISION_EVENTS,
+                YSort::new(-PICKUP_RADIUS),
+                Collectible {
+                    item,
+                    count: map_collectible.count,
+                    flag,
+                },
+                StateScoped(CurrentLocation(location.id)),
+            ))
+            .with_children(|parent| {
+                parent.spawn((
+                    SpriteBundle {
+                        texture: asset_server.load(item.texture()),
+                        ..default()
+                    },
+                    CollectibleSprite { phase },
+                ));
+            });
+    }
+}
+
+/// Squashing the sprite horizontally reads as a spin for a flat coin.
+pub fn animate_collectibles(
+    time: Res<Time>,
+    mut sprite_query: Query<(&CollectibleSprite, &mut Transform)>,
+) {
+    let elapsed = time.elapsed_seconds();
+
+    for (sprite, mut transform) in sprite_query.iter_mut() {
+        transform.translation.y = ((elapsed * BOB_SPEED + sprite.phase) * TAU).sin() * BOB_HEIGHT;
+        transform.scale.x = ((elapsed * SPIN_SPEED + sprite.phase) * TAU).cos();
+    }
+}
+
+pub fn pick_up_collectibles(
+    mut commands: Commands,
+    mut collision_reader: EventReader<CollisionEvent>,
+    mut world_flags: ResMut<WorldFlags>,
+    mut play_sfx_writer: EventWriter<PlaySfxEvent>,
+    mut player_query: Query<&mut Inventory, With<Player>>,
+    collectible_query: Query<(&Collectible, &Transform)>,
+) {
+    for event in collision_reader.read() {
+        let CollisionEvent::Started(first, second, _) = *event else {
+            continue;
+        };
+        let Some((player, collectible_entity)) = [(first, second), (second, first)]
+            .into_iter()
+            .find(|(player, collectible)| {
+                player_query.contains(*player) && collectible_query.contains(*collectible)
+            })
+        else {
+            continue;
+        };
+        let (Ok(mut inventory), Ok((collectible, transform))) = (
+            player_query.get_mut(player),
+            collectible_query.get(collectible_entity),
+        ) else {
+            continue;
+        };
+
+        info!("Picked up {} {:?}", collectible.count, collectible.item);
+        inventory.add(collectible.item, collectible.count);
+        world_flags.set(&collectible.flag);
+        play_sfx_writer.send(PlaySfxEvent::at(SfxId::CoinPick, transform.translation));
+        commands.entity(collectible_entity).despawn_recursive();
+    }
+}
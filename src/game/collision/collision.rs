@@ -0,0 +1,138 @@
+use std::f32::consts::TAU;
+
+use bevy::prelude::{
+    in_state, warn, Added, App, Assets, Commands, Component, Entity, GlobalTransform,
+    IntoSystemConfigs, Plugin, PostUpdate, Query, Rect, Res, Transform, TransformSystem, Update,
+    Vec2, Without,
+};
+use bevy_rapier2d::prelude::{Collider, PhysicsSet, RigidBody};
+
+use crate::game::movement::movement::MoveVelocity;
+use crate::tilemap::tiled::{
+    MapCollider, MapObject, MapShape, TiledMap, TiledMapHandle, TiledMapSpawned,
+};
+use crate::AppState;
+
+/// Number of points used to approximate ellipses that are not circles.
+const ELLIPSE_SEGMENTS: usize = 16;
+
+pub struct CollisionPlugin;
+
+impl Plugin for CollisionPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, (spawn_map_colliders, insert_world_bounds))
+            .add_systems(
+                PostUpdate,
+                clamp_to_world_bounds
+                    .after(PhysicsSet::Writeback)
+                    .before(TransformSystem::TransformPropagate)
+                    .run_if(in_state(AppState::Game)),
+            );
+    }
+}
+
+/// Area moving entities are kept in, relative to the entity it is on. Locations get
+/// one from their map, so nothing can walk off into the void past the colliders.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct WorldBounds {
+    pub rect: Rect,
+}
+
+impl WorldBounds {
+    pub fn new(rect: Rect) -> Self {
+        Self { rect }
+    }
+
+    pub fn to_world(self, transform: &GlobalTransform) -> Rect {
+        Rect::from_corners(
+            transform
+                .transform_point(self.rect.min.extend(0.0))
+                .truncate(),
+            transform
+                .transform_point(self.rect.max.extend(0.0))
+                .truncate(),
+        )
+    }
+}
+
+/// Solid shape of a map object, in the object's space. Points have no area and get none.
+pub fn collider_from_shape(shape: &MapShape) -> Option<Collider> {
+    match shape {
+        MapShape::Point => None,
+        MapShape::Rect(size) => Some(Collider::cuboid(size.x / 2.0, size.y / 2.0)),
+        MapShape::Ellipse(size) if size.x == size.y => Some(Collider::ball(size.x / 2.0)),
+        MapShape::Ellipse(size) => {
+            let points: Vec<Vec2> = (0..ELLIPSE_SEGMENTS)
+                .map(|index| {
+                    let angle = index as f32 / ELLIPSE_SEGMENTS as f32 * TAU;
+                    Vec2::from_angle(angle) * *size / 2.0
+                })
+                .collect();
+            Collider::convex_polyline(points)
+        }
+        // Polygons drawn in the editor may be concave, so they are split into convex parts.
+        MapShape::Polygon(points) => {
+            let edges: Vec<[u32; 2]> = (0..points.len() as u32)
+                .map(|index| [index, (index + 1) % points.len() as u32])
+                .collect();
+            Some(Collider::convex_decomposition(points, &edges))
+        }
+    }
+}
+
+/// Turns `collider` objects of the spawned maps into fixed bodies.
+pub fn spawn_map_colliders(
+    mut commands: Commands,
+    collider_query: Query<(Entity, &MapObject), Added<MapCollider>>,
+) {
+    for (entity, object) in collider_query.iter() {
+        let Some(collider) = collider_from_shape(&object.shape) else {
+            warn!("Map collider {:?} has no usable shape", object.name);
+            continue;
+        };
+
+        commands.entity(entity).insert((RigidBody::Fixed, collider));
+    }
+}
+
+pub fn insert_world_bounds(
+    mut commands: Commands,
+    tiled_maps: Res<Assets<TiledMap>>,
+    map_query: Query<(Entity, &TiledMapHandle), Added<TiledMapSpawned>>,
+) {
+    for (entity, map_handle) in map_query.iter() {
+        if let Some(map) = tiled_maps.get(&map_handle.0) {
+            commands
+                .entity(entity)
+                .insert(WorldBounds::new(map.bounds()));
+        }
+    }
+}
+
+/// Runs after rapier has moved the entities, so a push from the character controller
+/// can not carry them out either. Velocity towards the edge is dropped.
+pub fn clamp_to_world_bounds(
+    bounds_query: Query<(&WorldBounds, &GlobalTransform)>,
+    mut moving_query: Query<(&mut Transform, &mut MoveVelocity), Without<WorldBounds>>,
+) {
+    let Ok((bounds, bounds_transform)) = bounds_query.get_single() else {
+        return;
+    };
+    let rect = bounds.to_world(bounds_transform);
+
+    for (mut transform, mut velocity) in moving_query.iter_mut() {
+        let position = transform.translation.truncate();
+        let clamped = position.clamp(rect.min, rect.max);
+        if clamped == position {
+            continue;
+        }
+
+        if clamped.x != position.x {
+            velocity.x = 0.0;
+        }
+        if clamped.y != position.y {
+            velocity.y = 0.0;
+        }
+        transform.translation = clamped.extend(transform.translation.z);
+    }
+}
@@ -1,12 +1,21 @@
 use std::cmp::PartialEq;
 use std::collections::{HashMap, HashSet};
-use std::ops::Add;
+use std::io;
 
-use bevy::prelude::{in_state, info, App, ButtonInput, Component, Deref, IntoSystemConfigs, KeyCode, Plugin, Query, Res, ResMut, Resource, Update, Event, EventWriter, Entity};
+use bevy::input::gamepad::GamepadConnectionEvent;
+use bevy::prelude::{error, DetectChangesMut, in_state, info, App, Axis, ButtonInput, Component, Deref, EventReader, Gamepad, GamepadAxis, GamepadAxisType, GamepadButton, GamepadButtonType, Gamepads, IntoSystemConfigs, KeyCode, Plugin, Query, Res, ResMut, Resource, Update, Event, EventWriter, Vec2};
+use bevy::prelude::KeyCode::{
+    ArrowDown, ArrowLeft, ArrowRight, ArrowUp, Enter, Escape, KeyA, KeyD, KeyF, KeyS, KeyW,
+    ShiftLeft,
+};
+use serde::{Deserialize, Serialize};
 
-use crate::game::game::GameState;
-use crate::game::movement::movement::{Direction, MoveEndEvent};
-use crate::AppState;
+use crate::game::camera::camera::{ZOOM_IN_KEY, ZOOM_OUT_KEY};
+use crate::game::game::{GameState, PAUSE_KEY};
+use crate::game::movement::movement::Direction;
+use crate::settings::settings::{load_settings, save_settings};
+use crate::systems::{MAIN_MENU_KEY, START_GAME_KEY};
+use crate::ui::in_game::CONTROLS_HINT_KEY;
 
 pub struct ControlsPlugin;
 
@@ -14,8 +23,17 @@ impl Plugin for ControlsPlugin {
     fn build(&self, app: &mut App) {
         app
             .init_resource::<Actions>()
+            .init_resource::<PendingRebind>()
+            .init_resource::<InputDevice>()
+            .insert_resource(ControlBindings::load())
             .add_event::<ActionEvent>()
             .add_event::<ActionEndEvent>()
+            .add_event::<RebindRequest>()
+            .add_event::<RebindEvent>()
+            .add_systems(
+                Update,
+                (bind_gamepads, detect_input_device, (start_rebind, capture_rebind).chain()),
+            )
             .add_systems(
                 Update,
                 handle_controls_state.run_if(in_state(GameState::Running)),
@@ -23,9 +41,231 @@ impl Plugin for ControlsPlugin {
     }
 }
 
+pub const DEFAULT_STICK_DEAD_ZONE: f32 = 0.2;
+/// Keyboard bindings each action has, rebound one at a time.
+pub const KEY_SLOTS: usize = 2;
+
+/// Keys the game handles itself, with what they do. Binding them to an action would
+/// trigger both, e.g. moving up and leaving for the main menu.
+const RESERVED_KEYS: [(KeyCode, &str); 8] = [
+    (Escape, "Quit"),
+    (Enter, "Menu confirm"),
+    (PAUSE_KEY, "Pause"),
+    (START_GAME_KEY, "Start game"),
+    (MAIN_MENU_KEY, "Main menu"),
+    (CONTROLS_HINT_KEY, "Controls hint"),
+    (ZOOM_IN_KEY, "Zoom in"),
+    (ZOOM_OUT_KEY, "Zoom out"),
+];
+/// Arrows navigate menus, which never take input while the player moves, so only
+/// movement may share them.
+const MENU_ARROW_KEYS: [KeyCode; 4] = [ArrowUp, ArrowLeft, ArrowDown, ArrowRight];
+
+/// Marks an entity driven by the player's input. Which keys and buttons map to which
+/// actions is shared through the [`ControlBindings`] resource.
 #[derive(Component, Debug)]
 pub struct Controls {
-    pub controls_map: HashMap<KeyCode, ControlledAction>,
+    /// Gamepad driving this entity alongside the keyboard, bound on connection.
+    pub gamepad: Option<Gamepad>,
+    /// Radial dead zone of the left stick, tilts below it are ignored.
+    pub stick_dead_zone: f32,
+}
+
+impl Default for Controls {
+    fn default() -> Self {
+        Self {
+            gamepad: None,
+            stick_dead_zone: DEFAULT_STICK_DEAD_ZONE,
+        }
+    }
+}
+
+impl Controls {
+    /// Left stick of the bound gamepad with the dead zone cut out and the live zone
+    /// rescaled, so the movement starts from zero right at the dead zone edge.
+    pub fn read_stick(&self, gamepad_axes: &Axis<GamepadAxis>) -> Vec2 {
+        let Some(gamepad) = self.gamepad else {
+            return Vec2::ZERO;
+        };
+
+        let stick = Vec2::new(
+            gamepad_axes.get(GamepadAxis::new(gamepad, GamepadAxisType::LeftStickX)).unwrap_or(0.0),
+            gamepad_axes.get(GamepadAxis::new(gamepad, GamepadAxisType::LeftStickY)).unwrap_or(0.0),
+        );
+        let tilt = stick.length();
+
+        if tilt <= self.stick_dead_zone {
+            return Vec2::ZERO;
+        }
+
+        let live_tilt = ((tilt - self.stick_dead_zone) / (1.0 - self.stick_dead_zone)).min(1.0);
+
+        stick / tilt * live_tilt
+    }
+}
+
+/// Key and button bindings, persisted to `controls.ron` in the user config directory.
+#[derive(Resource, Debug, Clone, Serialize, Deserialize)]
+pub struct ControlBindings {
+    /// Keys of each action by slot, a slot keeps its place when the others are rebound.
+    pub keyboard: HashMap<ControlledAction, [Option<KeyCode>; KEY_SLOTS]>,
+    pub gamepad: HashMap<GamepadButtonType, ControlledAction>,
+}
+
+impl Default for ControlBindings {
+    fn default() -> Self {
+        Self {
+            keyboard: HashMap::from([
+                (ControlledAction::MoveUp, [Some(KeyW), Some(ArrowUp)]),
+                (ControlledAction::MoveLeft, [Some(KeyA), Some(ArrowLeft)]),
+                (ControlledAction::MoveDown, [Some(KeyS), Some(ArrowDown)]),
+                (ControlledAction::MoveRight, [Some(KeyD), Some(ArrowRight)]),
+                (ControlledAction::Run, [Some(ShiftLeft), None]),
+                (ControlledAction::Attack, [Some(KeyF), None]),
+            ]),
+            gamepad: HashMap::from([
+                (GamepadButtonType::DPadUp, ControlledAction::MoveUp),
+                (GamepadButtonType::DPadLeft, ControlledAction::MoveLeft),
+                (GamepadButtonType::DPadDown, ControlledAction::MoveDown),
+                (GamepadButtonType::DPadRight, ControlledAction::MoveRight),
+                (GamepadButtonType::RightTrigger2, ControlledAction::Run),
+                (GamepadButtonType::West, ControlledAction::Attack),
+            ]),
+        }
+    }
+}
+
+impl ControlBindings {
+    const FILE_NAME: &'static str = "controls.ron";
+
+    pub fn load() -> Self {
+        load_settings(Self::FILE_NAME)
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        save_settings(Self::FILE_NAME, self)
+    }
+
+    /// Bound keys of `action` in slot order, empty slots are skipped.
+    pub fn keys_for(&self, action: ControlledAction) -> Vec<KeyCode> {
+        self.keyboard
+            .get(&action)
+            .map(|slots| slots.iter().flatten().copied().collect())
+            .unwrap_or_default()
+    }
+
+    pub fn key_in_slot(&self, action: ControlledAction, slot: usize) -> Option<KeyCode> {
+        self.keyboard
+            .get(&action)
+            .and_then(|slots| slots.get(slot).copied().flatten())
+    }
+
+    pub fn action_for_key(&self, key: KeyCode) -> Option<ControlledAction> {
+        self.keyboard
+            .iter()
+            .find(|(_, slots)| slots.contains(&Some(key)))
+            .map(|(action, _)| *action)
+    }
+
+    /// Prompt names of everything that triggers `action` on `device`.
+    pub fn glyphs_for(&self, action: ControlledAction, device: InputDevice) -> Vec<String> {
+        match device {
+            InputDevice::Keyboard => self.keys_for(action).iter().map(key_glyph).collect(),
+            InputDevice::Gamepad => self.buttons_for(action).iter().map(button_glyph).collect(),
+        }
+    }
+
+    pub fn buttons_for(&self, action: ControlledAction) -> Vec<GamepadButtonType> {
+        let mut buttons: Vec<GamepadButtonType> = self
+            .gamepad
+            .iter()
+            .filter(|(_, bound_action)| **bound_action == action)
+            .map(|(button, _)| *button)
+            .collect();
+        buttons.sort_by_key(|button| format!("{:?}", button));
+
+        buttons
+    }
+
+    /// Action that already uses `key` outside `slot` of `action`. It can be `action` itself,
+    /// binding its other key twice would drop that key.
+    pub fn conflict(
+        &self,
+        key: KeyCode,
+        action: ControlledAction,
+        slot: usize,
+    ) -> Option<ControlledAction> {
+        if self.key_in_slot(action, slot) == Some(key) {
+            return None;
+        }
+
+        self.action_for_key(key)
+    }
+
+    /// What the game itself does on `key`, if binding it to `action` would clash with that.
+    pub fn reserved(key: KeyCode, action: ControlledAction) -> Option<&'static str> {
+        if MENU_ARROW_KEYS.contains(&key) && !action.is_move_action() {
+            return Some("Menu navigation");
+        }
+
+        RESERVED_KEYS
+            .iter()
+            .find(|(reserved_key, _)| *reserved_key == key)
+            .map(|(_, usage)| *usage)
+    }
+
+    /// Replaces the key in `slot` of `action`, its other keyboard bindings stay.
+    pub fn rebind_key(&mut self, action: ControlledAction, slot: usize, key: KeyCode) {
+        if let Some(bound_key) = self.keyboard.entry(action).or_default().get_mut(slot) {
+            *bound_key = Some(key);
+        }
+    }
+}
+
+/// Device the player touched last, prompts show its keys or buttons.
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum InputDevice {
+    #[default]
+    Keyboard,
+    Gamepad,
+}
+
+/// Asks to bind the next pressed key to `slot` of `action`.
+#[derive(Event, Debug)]
+pub struct RebindRequest {
+    pub action: ControlledAction,
+    pub slot: usize,
+}
+
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RebindEvent {
+    Bound {
+        action: ControlledAction,
+        key: KeyCode,
+    },
+    /// The key is already used by `existing`, the bindings were left untouched.
+    Conflict {
+        action: ControlledAction,
+        key: KeyCode,
+        existing: ControlledAction,
+    },
+    /// The game itself uses the key for `usage`, the bindings were left untouched.
+    Reserved {
+        action: ControlledAction,
+        key: KeyCode,
+        usage: &'static str,
+    },
+    Cancelled {
+        action: ControlledAction,
+    },
+}
+
+/// Action waiting for a key press after a [`RebindRequest`].
+#[derive(Resource, Debug, Default)]
+pub struct PendingRebind {
+    pub action: Option<ControlledAction>,
+    /// Which of the action's keys gets replaced.
+    pub slot: usize,
 }
 
 #[derive(Resource, Debug, Default, Deref)]
@@ -33,9 +273,11 @@ pub struct Actions {
     pub current_actions: HashSet<ControlledAction>,
 }
 
-#[derive(Event, Debug, Eq, PartialEq)]
+#[derive(Event, Debug, PartialEq)]
 pub struct ActionEvent {
-    pub actions: HashSet<ControlledAction>
+    pub actions: HashSet<ControlledAction>,
+    /// Movement vector of the pressed actions, zero when opposing directions cancel out.
+    pub axis: Vec2,
 }
 
 #[derive(Event, Debug, Eq, PartialEq)]
@@ -53,11 +295,27 @@ impl ActionEndEvent {
 
 impl ActionEvent {
     pub fn new(actions: HashSet<ControlledAction>) -> Self {
+        let axis = Direction::axis_from_actions(&actions);
+
         Self {
-            actions
+            actions,
+            axis,
         }
     }
 
+    /// Replaces the digital movement axis with an analog one, e.g. from a gamepad stick.
+    pub fn with_axis(mut self, axis: Vec2) -> Self {
+        if axis != Vec2::ZERO {
+            self.axis = axis;
+        }
+
+        self
+    }
+
+    pub fn is_moving(&self) -> bool {
+        self.axis != Vec2::ZERO
+    }
+
     pub fn contains_running(&self) -> bool {
         self.actions.contains(&ControlledAction::Run)
     }
@@ -81,7 +339,7 @@ impl ActionEvent {
     }
 }
 
-#[derive(Hash, Eq, Debug, Copy, Clone, Default, PartialEq)]
+#[derive(Hash, Eq, Debug, Copy, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub enum ControlledAction {
     #[default]
     None,
@@ -106,6 +364,16 @@ impl Actions {
 }
 
 impl ControlledAction {
+    /// Actions a player can bind keys to, in the order they are listed in the settings.
+    pub const BINDABLE_ACTIONS: [Self; 6] = [
+        ControlledAction::MoveUp,
+        ControlledAction::MoveLeft,
+        ControlledAction::MoveDown,
+        ControlledAction::MoveRight,
+        ControlledAction::Run,
+        ControlledAction::Attack,
+    ];
+
     const MOVE_ACTIONS: [Self; 4] = [
         ControlledAction::MoveUp,
         ControlledAction::MoveDown,
@@ -125,6 +393,18 @@ impl ControlledAction {
         }
     }
 
+    pub fn label(&self) -> &'static str {
+        match self {
+            ControlledAction::None => "None",
+            ControlledAction::MoveUp => "Move Up",
+            ControlledAction::MoveLeft => "Move Left",
+            ControlledAction::MoveDown => "Move Down",
+            ControlledAction::MoveRight => "Move Right",
+            ControlledAction::Run => "Run",
+            ControlledAction::Attack => "Attack",
+        }
+    }
+
     pub fn is_move_action(&self) -> bool {
         Self::MOVE_ACTIONS.contains(self)
     }
@@ -132,29 +412,294 @@ impl ControlledAction {
     pub fn is_run_action(&self) -> bool {
         *self == ControlledAction::Run
     }
+
+    /// Move actions of a stick vector that already went through the dead zone.
+    pub fn from_stick(stick: Vec2) -> Vec<ControlledAction> {
+        let mut actions = Vec::new();
+
+        if stick.x > 0.0 {
+            actions.push(ControlledAction::MoveRight);
+        } else if stick.x < 0.0 {
+            actions.push(ControlledAction::MoveLeft);
+        }
+
+        if stick.y > 0.0 {
+            actions.push(ControlledAction::MoveUp);
+        } else if stick.y < 0.0 {
+            actions.push(ControlledAction::MoveDown);
+        }
+
+        actions
+    }
+}
+
+pub fn bind_gamepads(
+    mut connection_events: EventReader<GamepadConnectionEvent>,
+    gamepads: Res<Gamepads>,
+    mut query: Query<&mut Controls>,
+) {
+    for event in connection_events.read() {
+        if event.connected() {
+            info!("Gamepad connected: {:?}", event.gamepad);
+        } else {
+            info!("Gamepad disconnected: {:?}", event.gamepad);
+        }
+    }
+
+    for mut controls in query.iter_mut() {
+        if let Some(gamepad) = controls.gamepad {
+            if !gamepads.contains(gamepad) {
+                info!("Unbinding disconnected gamepad: {:?}", gamepad);
+                controls.gamepad = None;
+            }
+        }
+    }
+
+    // Controls spawned after a gamepad was plugged in never see its connection event,
+    // so free gamepads are bound by looking at the connected ones rather than at events.
+    let mut bound: Vec<Gamepad> = query.iter().filter_map(|controls| controls.gamepad).collect();
+
+    for mut controls in query.iter_mut() {
+        if controls.gamepad.is_some() {
+            continue;
+        }
+
+        let Some(free_gamepad) = gamepads.iter().find(|gamepad| !bound.contains(gamepad)) else {
+            break;
+        };
+
+        info!("Binding gamepad: {:?}", free_gamepad);
+        controls.gamepad = Some(free_gamepad);
+        bound.push(free_gamepad);
+    }
+}
+
+/// Short human readable name of a key, `KeyW` becomes `W` and `Digit1` becomes `1`.
+pub fn key_label(key: &KeyCode) -> String {
+    let name = format!("{:?}", key);
+
+    name.strip_prefix("Key")
+        .or_else(|| name.strip_prefix("Digit"))
+        .unwrap_or(&name)
+        .to_string()
+}
+
+/// Compact name of a key for on-screen prompts, arrows are drawn as arrows.
+pub fn key_glyph(key: &KeyCode) -> String {
+    match key {
+        ArrowUp => "\u{2191}".to_string(),
+        ArrowLeft => "\u{2190}".to_string(),
+        ArrowDown => "\u{2193}".to_string(),
+        ArrowRight => "\u{2192}".to_string(),
+        ShiftLeft => "LShift".to_string(),
+        KeyCode::ShiftRight => "RShift".to_string(),
+        _ => key_label(key),
+    }
+}
+
+/// Compact name of a gamepad button for on-screen prompts, face buttons use the Xbox letters.
+pub fn button_glyph(button: &GamepadButtonType) -> String {
+    let glyph = match button {
+        GamepadButtonType::South => "A",
+        GamepadButtonType::East => "B",
+        GamepadButtonType::West => "X",
+        GamepadButtonType::North => "Y",
+        GamepadButtonType::LeftTrigger => "LB",
+        GamepadButtonType::RightTrigger => "RB",
+        GamepadButtonType::LeftTrigger2 => "LT",
+        GamepadButtonType::RightTrigger2 => "RT",
+        GamepadButtonType::Select => "Back",
+        GamepadButtonType::Start => "Start",
+        GamepadButtonType::DPadUp => "D\u{2191}",
+        GamepadButtonType::DPadLeft => "D\u{2190}",
+        GamepadButtonType::DPadDown => "D\u{2193}",
+        GamepadButtonType::DPadRight => "D\u{2192}",
+        other => return format!("{:?}", other),
+    };
+
+    glyph.to_string()
+}
+
+pub fn rebind_in_progress(pending_rebind: Res<PendingRebind>) -> bool {
+    pending_rebind.action.is_some()
+}
+
+pub fn start_rebind(
+    mut request_reader: EventReader<RebindRequest>,
+    mut pending_rebind: ResMut<PendingRebind>,
+) {
+    for request in request_reader.read() {
+        info!("Waiting for a key to bind to {:?}", request.action);
+        pending_rebind.action = Some(request.action);
+        pending_rebind.slot = request.slot;
+    }
+}
+
+pub fn capture_rebind(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut pending_rebind: ResMut<PendingRebind>,
+    mut bindings: ResMut<ControlBindings>,
+    mut rebind_event_writer: EventWriter<RebindEvent>,
+) {
+    let Some(action) = pending_rebind.action else {
+        return;
+    };
+
+    let Some(key) = keyboard_input.get_just_pressed().next().copied() else {
+        return;
+    };
+
+    pending_rebind.action = None;
+
+    if key == Escape {
+        info!("Rebinding of {:?} cancelled", action);
+        rebind_event_writer.send(RebindEvent::Cancelled { action });
+        return;
+    }
+
+    if let Some(usage) = ControlBindings::reserved(key, action) {
+        info!("{:?} is reserved for {}", key, usage);
+        rebind_event_writer.send(RebindEvent::Reserved { action, key, usage });
+        return;
+    }
+
+    if let Some(existing) = bindings.conflict(key, action, pending_rebind.slot) {
+        info!("{:?} is already bound to {:?}", key, existing);
+        rebind_event_writer.send(RebindEvent::Conflict { action, key, existing });
+        return;
+    }
+
+    bindings.rebind_key(action, pending_rebind.slot, key);
+    info!("Bound {:?} to {:?}", key, action);
+
+    if let Err(save_error) = bindings.save() {
+        error!("Failed to save control bindings: {}", save_error);
+    }
+
+    rebind_event_writer.send(RebindEvent::Bound { action, key });
+}
+
+pub fn detect_input_device(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    gamepad_buttons: Res<ButtonInput<GamepadButton>>,
+    gamepad_axes: Res<Axis<GamepadAxis>>,
+    query: Query<&Controls>,
+    mut input_device: ResMut<InputDevice>,
+) {
+    let stick_moved = query
+        .iter()
+        .any(|controls| controls.read_stick(&gamepad_axes) != Vec2::ZERO);
+
+    if gamepad_buttons.get_just_pressed().next().is_some() || stick_moved {
+        input_device.set_if_neq(InputDevice::Gamepad);
+    } else if keyboard_input.get_just_pressed().next().is_some() {
+        input_device.set_if_neq(InputDevice::Keyboard);
+    }
 }
 
 pub fn handle_controls_state(
     keyboard_input: Res<ButtonInput<KeyCode>>,
+    gamepad_buttons: Res<ButtonInput<GamepadButton>>,
+    gamepad_axes: Res<Axis<GamepadAxis>>,
+    bindings: Res<ControlBindings>,
     mut event_writer: EventWriter<ActionEvent>,
-    mut query: Query<&Controls>,
+    query: Query<&Controls>,
 ) {
-    let pressed_keys: HashSet<KeyCode> = keyboard_input.get_pressed().cloned().collect();
-    let released_keys: HashSet<KeyCode> = keyboard_input.get_just_released().cloned().collect();
+    let controls = query.single();
+    let mut new_actions: HashSet<ControlledAction> = keyboard_input
+        .get_pressed()
+        .filter_map(|pressed_key| bindings.action_for_key(*pressed_key))
+        .collect();
 
-    let controls = query.single_mut();
-    let mut new_actions = HashSet::new();
+    if let Some(gamepad) = controls.gamepad {
+        new_actions.extend(
+            gamepad_buttons
+                .get_pressed()
+                .filter(|button| button.gamepad == gamepad)
+                .filter_map(|button| bindings.gamepad.get(&button.button_type))
+                .copied(),
+        );
+    }
 
-    if !pressed_keys.is_empty() {
-        for pressed_key in pressed_keys.iter() {
-            if let Some(action) = controls.controls_map.get(pressed_key) {
-                new_actions.insert(*action);
-            }
-        }
+    let stick = controls.read_stick(&gamepad_axes);
+    new_actions.extend(ControlledAction::from_stick(stick));
 
+    if !new_actions.is_empty() {
         info!("Sending actions event: {:?}", &new_actions);
-        event_writer.send(ActionEvent::new(new_actions));
+        event_writer.send(ActionEvent::new(new_actions).with_axis(stick));
     } else {
         event_writer.send(ActionEvent::new(HashSet::from([ControlledAction::None])));
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rebinding_a_slot_keeps_the_other_keys() {
+        let mut bindings = ControlBindings::default();
+
+        bindings.rebind_key(ControlledAction::MoveUp, 1, KeyCode::KeyI);
+
+        assert_eq!(
+            bindings.keys_for(ControlledAction::MoveUp),
+            vec![KeyW, KeyCode::KeyI]
+        );
+        assert_eq!(bindings.action_for_key(ArrowUp), None);
+    }
+
+    #[test]
+    fn keys_of_another_slot_of_the_same_action_conflict() {
+        let bindings = ControlBindings::default();
+
+        assert_eq!(
+            bindings.conflict(ArrowUp, ControlledAction::MoveUp, 0),
+            Some(ControlledAction::MoveUp)
+        );
+        assert_eq!(bindings.conflict(ArrowUp, ControlledAction::MoveUp, 1), None);
+        assert_eq!(
+            bindings.conflict(ArrowUp, ControlledAction::MoveDown, 1),
+            Some(ControlledAction::MoveUp)
+        );
+    }
+
+    #[test]
+    fn rebinding_a_slot_keeps_its_place() {
+        let mut bindings = ControlBindings::default();
+
+        bindings.rebind_key(ControlledAction::MoveUp, 0, KeyCode::KeyZ);
+
+        assert_eq!(bindings.key_in_slot(ControlledAction::MoveUp, 0), Some(KeyCode::KeyZ));
+        assert_eq!(bindings.key_in_slot(ControlledAction::MoveUp, 1), Some(ArrowUp));
+    }
+
+    #[test]
+    fn rebinding_an_empty_slot_adds_a_key() {
+        let mut bindings = ControlBindings::default();
+
+        bindings.rebind_key(ControlledAction::Attack, 1, KeyCode::KeyJ);
+
+        assert_eq!(
+            bindings.keys_for(ControlledAction::Attack),
+            vec![KeyF, KeyCode::KeyJ]
+        );
+    }
+
+    #[test]
+    fn game_keys_are_reserved() {
+        for key in [Escape, Enter, PAUSE_KEY, MAIN_MENU_KEY, CONTROLS_HINT_KEY, ZOOM_OUT_KEY] {
+            assert!(ControlBindings::reserved(key, ControlledAction::MoveUp).is_some());
+        }
+        assert_eq!(ControlBindings::reserved(KeyCode::KeyI, ControlledAction::MoveUp), None);
+    }
+
+    #[test]
+    fn arrows_are_reserved_for_movement() {
+        assert_eq!(ControlBindings::reserved(ArrowDown, ControlledAction::MoveUp), None);
+        assert_eq!(
+            ControlBindings::reserved(ArrowDown, ControlledAction::Attack),
+            Some("Menu navigation")
+        );
+    }
+}
@@ -1,44 +1,99 @@
+use std::collections::HashSet;
+
 use bevy::prelude::{
-    default, in_state, info, App, AppExtStates, ButtonInput, Camera2dBundle, Commands,
-    IntoSystemConfigs, KeyCode, NextState, Plugin, Res, ResMut, Startup, State, States, Transform,
-    Update,
+    in_state, info, App, AppExtStates, ButtonInput, GamepadButton, GamepadButtonType,
+    IntoSystemConfigs, KeyCode, NextState, OnEnter, OnExit, Plugin, Res, ResMut, Resource, State,
+    StateSet, SubStates, Time, Update, Virtual,
 };
+use bevy_rapier2d::prelude::RapierConfiguration;
+use serde::{Deserialize, Serialize};
 
-use crate::animation::animation::PepaAnimationPlugin;
+use crate::game::camera::camera::CameraPlugin;
+use crate::game::collectible::collectible::CollectiblePlugin;
+use crate::game::collision::collision::CollisionPlugin;
 use crate::game::controls::controls::ControlsPlugin;
+use crate::game::health::health::HealthPlugin;
 use crate::game::movement::movement::MovementPlugin;
-use crate::game::npc::npc::NpcPlugin;
+use crate::game::objective::objective::ObjectivePlugin;
 use crate::game::player::player::PlayerPlugin;
-use crate::{AppState, WINDOW_HEIGHT, WINDOW_WIDTH};
+use crate::game::stamina::stamina::StaminaPlugin;
+use crate::ui::settings::SettingsState;
+use crate::AppState;
 
 pub struct GamePlugin;
 
-#[derive(States, Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub const PAUSE_KEY: KeyCode = KeyCode::Space;
+pub const PAUSE_BUTTON: GamepadButtonType = GamepadButtonType::Start;
+
+/// State of the running game session, it only exists while in [`AppState::Game`].
+/// Every session starts running, pausing freezes the world under the pause menu.
+#[derive(SubStates, Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[source(AppState = AppState::Game)]
 pub enum GameState {
     #[default]
-    Paused,
     Running,
+    Paused,
 }
 
 impl Plugin for GamePlugin {
     fn build(&self, app: &mut App) {
-        app.init_state::<GameState>()
+        app.add_sub_state::<GameState>()
+            .enable_state_scoped_entities::<GameState>()
+            .init_resource::<WorldFlags>()
             .add_plugins((
                 PlayerPlugin,
                 MovementPlugin,
                 ControlsPlugin,
+                CollisionPlugin,
+                CollectiblePlugin,
+                CameraPlugin,
+                HealthPlugin,
+                StaminaPlugin,
+                ObjectivePlugin,
                 //NpcPlugin
             ))
-            .add_systems(Startup, spawn_camera)
-            .add_systems(Update, (toggle_pause,).run_if(in_state(AppState::Game)));
+            .add_systems(OnEnter(GameState::Paused), freeze_world)
+            .add_systems(OnExit(GameState::Paused), unfreeze_world)
+            .add_systems(
+                Update,
+                toggle_pause
+                    .run_if(in_state(AppState::Game))
+                    .run_if(in_state(SettingsState::Closed)),
+            );
     }
 }
+
+/// Story progress flags, e.g. a door that was opened or a talk that already happened.
+#[derive(Resource, Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct WorldFlags {
+    pub flags: HashSet<String>,
+}
+
+impl WorldFlags {
+    pub fn set(&mut self, flag: &str) {
+        self.flags.insert(flag.to_string());
+    }
+
+    pub fn clear(&mut self, flag: &str) {
+        self.flags.remove(flag);
+    }
+
+    pub fn is_set(&self, flag: &str) -> bool {
+        self.flags.contains(flag)
+    }
+}
+
 pub fn toggle_pause(
     keyboard_input: Res<ButtonInput<KeyCode>>,
+    gamepad_buttons: Res<ButtonInput<GamepadButton>>,
     game_state: Res<State<GameState>>,
     mut next_state: ResMut<NextState<GameState>>,
 ) {
-    if keyboard_input.just_pressed(KeyCode::Space) {
+    let start_pressed = gamepad_buttons
+        .get_just_pressed()
+        .any(|button| button.button_type == PAUSE_BUTTON);
+
+    if keyboard_input.just_pressed(PAUSE_KEY) || start_pressed {
         match game_state.get() {
             GameState::Paused => {
                 next_state.set(GameState::Running);
@@ -52,9 +107,22 @@ pub fn toggle_pause(
     }
 }
 
-pub fn spawn_camera(mut commands: Commands) {
-    commands.spawn(Camera2dBundle {
-        transform: Transform::from_xyz(WINDOW_WIDTH / 2.0, WINDOW_HEIGHT / 2.0, 1.0),
-        ..default()
-    });
+pub fn freeze_world(
+    mut time: ResMut<Time<Virtual>>,
+    mut rapier_configuration: ResMut<RapierConfiguration>,
+) {
+    time.pause();
+    rapier_configuration.physics_pipeline_active = false;
+    info!("World frozen");
+}
+
+/// Runs on resume and also when the session ends while paused, so menus never
+/// inherit a stopped clock.
+pub fn unfreeze_world(
+    mut time: ResMut<Time<Virtual>>,
+    mut rapier_configuration: ResMut<RapierConfiguration>,
+) {
+    time.unpause();
+    rapier_configuration.physics_pipeline_active = true;
+    info!("World unfrozen");
 }
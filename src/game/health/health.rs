@@ -0,0 +1,85 @@
+use bevy::prelude::{
+    in_state, warn, App, Component, Entity, Event, EventReader, IntoSystemConfigs, Plugin, Query,
+    Update,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::game::game::GameState;
+
+pub const DEFAULT_MAX_HEALTH: f32 = 100.0;
+
+pub struct HealthPlugin;
+
+impl Plugin for HealthPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<DamageEvent>()
+            .add_systems(Update, apply_damage.run_if(in_state(GameState::Running)));
+    }
+}
+
+/// A hit landing on an entity, combat sends these instead of changing [`Health`] directly
+/// so others can react to the hit.
+#[derive(Event, Debug)]
+pub struct DamageEvent {
+    pub entity: Entity,
+    pub amount: f32,
+}
+
+impl DamageEvent {
+    pub fn new(entity: Entity, amount: f32) -> Self {
+        Self { entity, amount }
+    }
+}
+
+#[derive(Component, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Health {
+    pub current: f32,
+    pub max: f32,
+}
+
+impl Default for Health {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_HEALTH)
+    }
+}
+
+impl Health {
+    pub fn new(max: f32) -> Self {
+        Self { current: max, max }
+    }
+
+    pub fn damage(&mut self, amount: f32) {
+        self.current = (self.current - amount).max(0.0);
+    }
+
+    pub fn heal(&mut self, amount: f32) {
+        self.current = (self.current + amount).min(self.max);
+    }
+
+    pub fn is_dead(&self) -> bool {
+        self.current <= 0.0
+    }
+
+    /// Share of the maximum health left, from 0 to 1.
+    pub fn fraction(&self) -> f32 {
+        if self.max > 0.0 {
+            self.current / self.max
+        } else {
+            0.0
+        }
+    }
+}
+
+pub fn apply_damage(
+    mut damage_reader: EventReader<DamageEvent>,
+    mut health_query: Query<&mut Health>,
+) {
+    for event in damage_reader.read() {
+        let Ok(mut health) = health_query.get_mut(event.entity) else {
+            warn!("Entity {:?} has no health to damage", event.entity);
+            continue;
+        };
+
+        health.damage(event.amount);
+    }
+}
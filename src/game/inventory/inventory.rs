@@ -0,0 +1,53 @@
+use std::collections::HashMap;
+
+use bevy::prelude::Component;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ItemId {
+    Coin,
+}
+
+impl ItemId {
+    /// Item named `name` in maps, e.g. `coin`.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "coin" => Some(ItemId::Coin),
+            _ => None,
+        }
+    }
+
+    pub fn texture(&self) -> &'static str {
+        match self {
+            ItemId::Coin => "sprites/items/coin.png",
+        }
+    }
+}
+
+/// Items carried by an entity and how many of each.
+#[derive(Component, Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Inventory {
+    pub items: HashMap<ItemId, u32>,
+}
+
+impl Inventory {
+    pub fn add(&mut self, item: ItemId, count: u32) {
+        *self.items.entry(item).or_default() += count;
+    }
+
+    /// Takes `count` items out, returns false and leaves the inventory untouched
+    /// when there are not enough of them.
+    pub fn remove(&mut self, item: ItemId, count: u32) -> bool {
+        match self.items.get_mut(&item) {
+            Some(current) if *current >= count => {
+                *current -= count;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    pub fn count(&self, item: ItemId) -> u32 {
+        self.items.get(&item).copied().unwrap_or_default()
+    }
+}
@@ -1,92 +1,357 @@
-use crate::{AppState, WINDOW_HEIGHT, WINDOW_WIDTH};
+use std::collections::HashMap;
+
+use crate::AppState;
 
 use bevy::prelude::{
-    default, App, AssetServer, Commands, Component, Entity, OnEnter, Plugin, Query, Res,
-    SpriteBundle, Transform, Vec3, With,
+    in_state, info, not, resource_exists, state_changed, warn, App, AppExtStates, AssetServer,
+    Commands, Component, Entity, Event, EventWriter, Has, IntoSystemConfigs, NextState, OnExit,
+    Parent, Plugin, Query, Rect, Res, ResMut, Resource, State, StateScoped, StateSet, SubStates,
+    Transform, Update, Vec2, Vec3, With, Without,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::audio::audio::MusicId;
+use crate::game::game::GameState;
+use crate::game::player::player::Player;
+use crate::parallax::parallax::{ParallaxBundle, ParallaxLayer};
+use crate::tilemap::tiled::{
+    Door, MapObject, MapShape, SpawnPoint, TiledMapBundle, TiledMapSpawned,
 };
 
+/// Top left corner of every location map, the first 1024px station screen ends up
+/// centered on the world origin.
+const LOCATION_ORIGIN: Vec3 = Vec3::new(-512.0, 512.0, 0.0);
+/// The forest is drawn from 32px tiles, scaled up to match the characters.
+const FOREST_SCALE: f32 = 2.0;
+/// Size of the forest map in the world.
+const FOREST_SIZE: Vec2 = Vec2::new(48.0 * 32.0 * FOREST_SCALE, 32.0 * 32.0 * FOREST_SCALE);
+/// Depth of the falling leaves, above every character.
+const FOREGROUND_Z: f32 = 50.0;
+
+/// Spawn point a new game puts the player on.
+pub const START_SPAWN: &str = "start";
+
 pub struct LocationPlugin;
 
 impl Plugin for LocationPlugin {
     fn build(&self, app: &mut App) {
-        app
-            .add_systems(OnEnter(AppState::Game), spawn_station)
-            .add_systems(OnEnter(AppState::MainMenu), despawn_station_location);
+        app.add_sub_state::<CurrentLocation>()
+            .enable_state_scoped_entities::<CurrentLocation>()
+            .init_resource::<LocationRegistry>()
+            .add_event::<LocationChangedEvent>()
+            .add_systems(OnExit(AppState::Game), clear_location_entrance)
+            .add_systems(
+                Update,
+                (
+                    spawn_current_location.run_if(state_changed::<CurrentLocation>),
+                    enter_doors
+                        .run_if(in_state(GameState::Running))
+                        .run_if(not(resource_exists::<LocationEntrance>)),
+                    place_player_at_entrance.run_if(resource_exists::<LocationEntrance>),
+                )
+                    .chain()
+                    .run_if(in_state(AppState::Game)),
+            );
     }
 }
 
-#[derive(Component)]
-pub struct AutumnForestLocation {}
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum LocationId {
+    #[default]
+    Station,
+    AutumnForest,
+}
 
-#[derive(Component)]
-pub struct StationLocation {
-    width: f32,
-    height: f32,
+impl LocationId {
+    pub fn label(&self) -> &'static str {
+        match self {
+            LocationId::Station => "Station",
+            LocationId::AutumnForest => "Autumn Forest",
+        }
+    }
 }
 
-pub fn spawn_station(mut commands: Commands, asset_server: Res<AssetServer>) {
-    commands.spawn((
-        SpriteBundle {
-            texture: asset_server.load("sprites/locations/station/station-new-start-001.png"),
-            transform: Transform::from_xyz(WINDOW_WIDTH / 2.0, WINDOW_HEIGHT / 2.0, 0.0),
-            ..default()
-        },
-        StationLocation {
-            width: 1024.0,
-            height: 1024.0,
-        },
-    ));
+/// Location the player is in, it only exists while in [`AppState::Game`]. Entities of
+/// a location are scoped to it, so switching the state unloads the previous one.
+#[derive(SubStates, Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[source(AppState = AppState::Game)]
+pub struct CurrentLocation(pub LocationId);
 
-    let next_vec = Vec3::new(WINDOW_WIDTH / 2.0, WINDOW_HEIGHT / 2.0, 0.0) + Vec3::new(1024.0, 0.0, 0.0);
-    commands.spawn((
-        SpriteBundle {
-            texture: asset_server.load("sprites/locations/station/station-inner-001.png"),
-            transform: Transform::from_translation(next_vec),
-            ..default()
-        },
-        StationLocation {
-            width: 1024.0,
-            height: 1024.0,
-        },
-    ));
+/// Sent once the player has moved into another location.
+#[derive(Event, Debug)]
+pub struct LocationChangedEvent {
+    pub location: LocationId,
+}
+
+/// How a location is built.
+#[derive(Debug, Clone)]
+pub struct LocationDefinition {
+    /// Name doors refer to in their `location` property.
+    pub name: &'static str,
+    pub map: &'static str,
+    pub scale: f32,
+    /// Parallax layers drawn around the map, back to front.
+    pub backdrop: Vec<BackdropLayer>,
+    /// What the player should do here, shown on the HUD.
+    pub objective: Option<&'static str>,
+    /// Music crossfaded to when the player comes in, `None` keeps it quiet.
+    pub music: Option<MusicId>,
+}
+
+impl LocationDefinition {
+    pub fn new(name: &'static str, map: &'static str) -> Self {
+        Self {
+            name,
+            map,
+            scale: 1.0,
+            backdrop: Vec::new(),
+            objective: None,
+            music: None,
+        }
+    }
+
+    pub fn with_scale(mut self, scale: f32) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    pub fn with_backdrop(mut self, backdrop: Vec<BackdropLayer>) -> Self {
+        self.backdrop = backdrop;
+        self
+    }
+
+    pub fn with_objective(mut self, objective: &'static str) -> Self {
+        self.objective = Some(objective);
+        self
+    }
+
+    pub fn with_music(mut self, music: MusicId) -> Self {
+        self.music = Some(music);
+        self
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct BackdropLayer {
+    pub image: &'static str,
+    pub layer: ParallaxLayer,
+    pub z: f32,
+}
+
+impl BackdropLayer {
+    pub fn new(image: &'static str, layer: ParallaxLayer, z: f32) -> Self {
+        Self { image, layer, z }
+    }
+}
+
+/// Autumn scenery above the forest's top edge: far mountains, two rows of trees and
+/// leaves blowing past in front of everything.
+fn autumn_backdrop() -> Vec<BackdropLayer> {
+    let top_center = LOCATION_ORIGIN.truncate() + Vec2::new(FOREST_SIZE.x / 2.0, 0.0);
+    // Only the horizontal movement is slowed down, the scenery stays on the map's edge.
+    let layer = |height: f32, scroll_factor: f32| {
+        ParallaxLayer::new(
+            top_center + Vec2::new(0.0, height),
+            Vec2::new(scroll_factor, 1.0),
+        )
+    };
+
+    vec![
+        BackdropLayer::new("sprites/locations/autumn/5.png", layer(560.0, 0.2), -0.9),
+        BackdropLayer::new("sprites/locations/autumn/2.png", layer(340.0, 0.4), -0.8),
+        BackdropLayer::new("sprites/locations/autumn/3.png", layer(380.0, 0.6), -0.7),
+        // Stays on screen vertically, drifts sideways faster than the world.
+        BackdropLayer::new(
+            "sprites/locations/autumn/1.png",
+            ParallaxLayer::new(top_center, Vec2::new(1.3, 0.0)).with_auto_scroll(-60.0),
+            FOREGROUND_Z,
+        ),
+    ]
+}
+
+#[derive(Resource, Debug)]
+pub struct LocationRegistry {
+    pub locations: HashMap<LocationId, LocationDefinition>,
+}
+
+impl LocationRegistry {
+    pub fn get(&self, location: LocationId) -> Option<&LocationDefinition> {
+        self.locations.get(&location)
+    }
+
+    pub fn find_by_name(&self, name: &str) -> Option<LocationId> {
+        self.locations
+            .iter()
+            .find(|(_, definition)| definition.name == name)
+            .map(|(location, _)| *location)
+    }
+}
+
+impl Default for LocationRegistry {
+    fn default() -> Self {
+        Self {
+            locations: HashMap::from([
+                (
+                    LocationId::Station,
+                    LocationDefinition::new("station", "maps/station.tmj")
+                        .with_objective("Find the way out of the station")
+                        .with_music(MusicId::Station),
+                ),
+                (
+                    LocationId::AutumnForest,
+                    LocationDefinition::new("autumn_forest", "maps/forest.tmj")
+                        .with_scale(FOREST_SCALE)
+                        .with_backdrop(autumn_backdrop())
+                        .with_objective("Explore the autumn forest")
+                        .with_music(MusicId::Forest),
+                ),
+            ]),
+        }
+    }
+}
+
+/// Map entity of a loaded location.
+#[derive(Component, Debug)]
+pub struct Location {
+    pub id: LocationId,
+}
+
+/// Spawn point the player is moved to once the location it went into has loaded.
+#[derive(Resource, Debug)]
+pub struct LocationEntrance {
+    pub location: LocationId,
+    pub spawn: String,
+}
+
+pub fn spawn_current_location(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    registry: Res<LocationRegistry>,
+    current_location: Res<State<CurrentLocation>>,
+) {
+    let location = *current_location.get();
+    let Some(definition) = registry.get(location.0) else {
+        warn!("Location {:?} is not registered", location.0);
+        return;
+    };
+    info!("Spawning location {}", definition.name);
 
-    let next_vec = next_vec + Vec3::new(1024.0, 0.0, 0.0);
     commands.spawn((
-        SpriteBundle {
-            texture: asset_server.load("sprites/locations/station/station-inner-001.png"),
-            transform: Transform::from_translation(next_vec),
-            ..default()
-        },
-        StationLocation {
-            width: 1024.0,
-            height: 1024.0,
-        },
+        TiledMapBundle::new(
+            asset_server.load(definition.map),
+            Transform::from_translation(LOCATION_ORIGIN).with_scale(Vec3::splat(definition.scale)),
+        ),
+        Location { id: location.0 },
+        StateScoped(location),
     ));
-}
 
-pub fn spawn_forest_location(mut commands: Commands, asset_server: Res<AssetServer>) {
-    commands.spawn(SpriteBundle {
-        texture: asset_server.load("sprites/locations/autumn/forest_full.png"),
-        transform: Transform::from_xyz(WINDOW_WIDTH / 2.0, WINDOW_HEIGHT / 2.0, 0.0)
-            .with_scale(Vec3::new(0.7, 0.7, 0.0)),
-        ..default()
-    });
+    for backdrop in definition.backdrop.iter() {
+        commands.spawn((
+            ParallaxBundle::new(
+                asset_server.load(backdrop.image),
+                backdrop.layer.clone(),
+                backdrop.z,
+            ),
+            StateScoped(location),
+        ));
+    }
 }
 
-pub fn despawn_forest_location(
+/// Doors are trigger zones: walking into one switches to the location it leads to.
+pub fn enter_doors(
     mut commands: Commands,
-    location_query: Query<Entity, With<AutumnForestLocation>>,
+    registry: Res<LocationRegistry>,
+    mut next_location: ResMut<NextState<CurrentLocation>>,
+    player_query: Query<&Transform, With<Player>>,
+    door_query: Query<(&Door, &MapObject, &Parent)>,
+    map_query: Query<&Transform, Without<Player>>,
 ) {
-    if let Ok(location_entity) = location_query.get_single() {
-        commands.entity(location_entity).despawn()
+    let Ok(player_transform) = player_query.get_single() else {
+        return;
+    };
+    let player_position = player_transform.translation;
+
+    for (door, object, parent) in door_query.iter() {
+        let MapShape::Rect(size) = object.shape else {
+            continue;
+        };
+        let Ok(map_transform) = map_query.get(parent.get()) else {
+            continue;
+        };
+        let zone = Rect::from_corners(
+            map_to_world(map_transform, object.position - size / 2.0),
+            map_to_world(map_transform, object.position + size / 2.0),
+        );
+        if !zone.contains(player_position.truncate()) {
+            continue;
+        }
+
+        let Some(location) = registry.find_by_name(&door.location) else {
+            warn!(
+                "Door {:?} leads to unknown location {}",
+                object.name, door.location
+            );
+            continue;
+        };
+        info!("Entering {} through {:?}", door.location, object.name);
+
+        commands.insert_resource(LocationEntrance {
+            location,
+            spawn: door.spawn.clone(),
+        });
+        next_location.set(CurrentLocation(location));
+        return;
     }
 }
 
-pub fn despawn_station_location(
+/// Waits for the map of the new location to spawn, then puts the player on its spawn point.
+pub fn place_player_at_entrance(
     mut commands: Commands,
-    location_query: Query<Entity, With<StationLocation>>,
+    entrance: Res<LocationEntrance>,
+    current_location: Res<State<CurrentLocation>>,
+    mut location_changed_writer: EventWriter<LocationChangedEvent>,
+    mut player_query: Query<&mut Transform, With<Player>>,
+    spawn_point_query: Query<(&SpawnPoint, &MapObject, &Parent)>,
+    map_query: Query<(Entity, &Transform, &Location, Has<TiledMapSpawned>), Without<Player>>,
 ) {
-    if let Ok(location_entity) = location_query.get_single() {
-        commands.entity(location_entity).despawn()
+    let Ok(mut player_transform) = player_query.get_single_mut() else {
+        return;
+    };
+    // The state switches a frame after the door is entered, until then the previous
+    // location is still loaded.
+    let current = current_location.get().0;
+    if current != entrance.location {
+        return;
     }
+    let Some((map_entity, map_transform, _, _)) = map_query
+        .iter()
+        .find(|(_, _, location, is_spawned)| location.id == current && *is_spawned)
+    else {
+        return;
+    };
+
+    let spawn_point = spawn_point_query.iter().find(|(spawn_point, _, parent)| {
+        parent.get() == map_entity && spawn_point.name == entrance.spawn
+    });
+    match spawn_point {
+        Some((_, object, _)) => {
+            let position = map_to_world(map_transform, object.position);
+            player_transform.translation = position.extend(player_transform.translation.z);
+        }
+        None => warn!("No spawn point {} in {:?}", entrance.spawn, current),
+    }
+
+    commands.remove_resource::<LocationEntrance>();
+    location_changed_writer.send(LocationChangedEvent { location: current });
+}
+
+/// A door entered right before leaving the game must not move the player of the next one.
+pub fn clear_location_entrance(mut commands: Commands) {
+    commands.remove_resource::<LocationEntrance>();
+}
+
+fn map_to_world(map_transform: &Transform, position: Vec2) -> Vec2 {
+    map_transform
+        .transform_point(position.extend(0.0))
+        .truncate()
 }
@@ -1,6 +1,13 @@
+pub mod camera;
+pub mod collectible;
+pub mod collision;
 pub mod controls;
 pub mod game;
+pub mod health;
+pub mod inventory;
 pub mod location;
 pub mod movement;
 pub mod npc;
+pub mod objective;
 pub mod player;
+pub mod stamina;
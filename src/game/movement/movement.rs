@@ -1,12 +1,12 @@
 use std::collections::HashSet;
+use std::f32::consts::FRAC_PI_4;
 
-use bevy::ecs::observer::TriggerTargets;
 use bevy::prelude::*;
 use bevy_rapier2d::prelude::KinematicCharacterController;
-use rand::{random, Rng};
+use rand::Rng;
 
 use crate::AppState;
-use crate::game::controls::controls::{Actions, ControlledAction};
+use crate::game::controls::controls::ControlledAction;
 use crate::game::game::GameState;
 
 pub struct MovementPlugin;
@@ -18,19 +18,24 @@ impl Plugin for MovementPlugin {
             .add_event::<MoveEndEvent>()
             .add_systems(
             Update,
-            (update_position)
+            (apply_move_intent, integrate_velocity, update_position)
+                .chain()
                 .run_if(in_state(AppState::Game))
                 .run_if(in_state(GameState::Running)),
         );
     }
 }
 
+/// Movement intent: where the entity wants to go and whether it runs.
+/// How fast it gets there is decided by its [`MovementStats`].
+///
+/// `direction` is an arbitrary vector (keyboard axis, analog stick, mouse aim), its length
+/// is clamped to 1 so a half-tilted stick moves at half speed.
 #[derive(Event, Debug)]
 pub struct MoveEvent {
     pub entity: Entity,
-    pub direction: Direction,
-    pub acceleration: f32,
-    pub speed: f32,
+    pub direction: Vec2,
+    pub is_running: bool,
 }
 
 #[derive(Event, Debug)]
@@ -39,17 +44,78 @@ pub struct MoveEndEvent {
 }
 
 impl MoveEvent {
-    pub fn new(entity: &Entity, direction: Direction, acceleration: f32, speed: f32) -> Self {
+    pub fn new(entity: &Entity, direction: Vec2, is_running: bool) -> Self {
         Self {
             entity: *entity,
             direction,
+            is_running,
+        }
+    }
+}
+
+impl MoveEndEvent {
+    pub fn new(entity: &Entity) -> Self {
+        Self { entity: *entity }
+    }
+}
+
+/// Speed limits and acceleration curves of a moving entity, in pixels per second.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct MovementStats {
+    pub max_speed: f32,
+    pub run_max_speed: f32,
+    /// How quickly the velocity builds up towards the max speed.
+    pub acceleration: f32,
+    /// How quickly the velocity decays once there is no intent or the entity is over its max speed.
+    pub friction: f32,
+}
+
+impl MovementStats {
+    pub fn new(max_speed: f32, run_max_speed: f32, acceleration: f32, friction: f32) -> Self {
+        Self {
+            max_speed,
+            run_max_speed,
             acceleration,
-            speed,
+            friction,
+        }
+    }
+}
+
+/// Movement of a character in pixels per second, named apart from rapier's `Velocity`
+/// which belongs to dynamic bodies.
+#[derive(Component, Debug, Default, Clone, Copy, Deref, DerefMut)]
+pub struct MoveVelocity(pub Vec2);
+
+/// Last received [`MoveEvent`], kept until it is replaced or cleared by a [`MoveEndEvent`].
+#[derive(Component, Debug, Default, Clone, Copy)]
+pub struct MoveIntent {
+    pub direction: Vec2,
+    pub is_running: bool,
+}
+
+#[derive(Bundle, Default)]
+pub struct MovementBundle {
+    pub stats: MovementStats,
+    pub velocity: MoveVelocity,
+    pub intent: MoveIntent,
+}
+
+impl MovementBundle {
+    pub fn new(stats: MovementStats) -> Self {
+        Self {
+            stats,
+            ..default()
         }
     }
 }
 
-#[derive(Default, Copy, Clone, Debug, Eq, Hash)]
+impl Default for MovementStats {
+    fn default() -> Self {
+        Self::new(100.0, 200.0, 800.0, 600.0)
+    }
+}
+
+#[derive(Default, Copy, Clone, Debug, PartialEq, Eq, Hash)]
 pub enum Direction {
     #[default]
     Zero,
@@ -64,24 +130,6 @@ pub enum Direction {
     DownLeft,
 }
 
-impl PartialEq for Direction {
-    fn eq(&self, other: &Self) -> bool {
-        match (self, other) {
-            (Direction::Up, Direction::Up) => true,
-            (Direction::Down, Direction::Down) => true,
-            (Direction::Left, Direction::Left) => true,
-            (Direction::Right, Direction::Right) => true,
-            (Direction::UpRight, Direction::UpRight) => true,
-            (Direction::UpLeft, Direction::UpLeft) => true,
-            (Direction::DownRight, Direction::DownRight) => true,
-            (Direction::DownLeft, Direction::DownLeft) => true,
-            (Direction::Random, Direction::Random) => true,
-            (Direction::Zero, Direction::Zero) => true,
-            _ => false,
-        }
-    }
-}
-
 impl Direction {
     pub const DIRECTIONS: [Self; 8] = [
         Direction::Up,
@@ -100,76 +148,42 @@ impl Direction {
         Direction::DownLeft
     ];
 
-    pub fn from_actions(actions: HashSet<ControlledAction>) -> Direction {
-        if actions.contains(&ControlledAction::MoveUp) && actions.contains(&ControlledAction::MoveRight) {
-            return Direction::UpRight;
-        }
-
-        if actions.contains(&ControlledAction::MoveDown) && actions.contains(&ControlledAction::MoveLeft) {
-            return Direction::DownLeft;
-        }
-
-        if actions.contains(&ControlledAction::MoveDown) && actions.contains(&ControlledAction::MoveRight) {
-            return Direction::DownRight;
-        }
-
-        if actions.contains(&ControlledAction::MoveUp) && actions.contains(&ControlledAction::MoveLeft) {
-            return Direction::UpLeft;
-        }
-
-        if actions.contains(&ControlledAction::MoveUp) {
-            return Direction::Up;
-        }
-
-        if actions.contains(&ControlledAction::MoveDown) {
-            return Direction::Down;
-        }
-
-        if actions.contains(&ControlledAction::MoveLeft) {
-            return Direction::Left;
-        }
-
-        if actions.contains(&ControlledAction::MoveRight) {
-            return Direction::Right;
-        }
+    /// Counter-clockwise from [`Direction::Right`], one entry per 45 degree sector.
+    const SECTORS: [Self; 8] = [
+        Direction::Right,
+        Direction::UpRight,
+        Direction::Up,
+        Direction::UpLeft,
+        Direction::Left,
+        Direction::DownLeft,
+        Direction::Down,
+        Direction::DownRight,
+    ];
 
-        Direction::Zero
+    pub fn from_actions(actions: HashSet<ControlledAction>) -> Direction {
+        Direction::from_vec(Direction::axis_from_actions(&actions))
     }
 
-    pub fn vec_from_actions(actions: Vec<ControlledAction>) -> Vec3 {
-        if actions.contains(&ControlledAction::MoveUp) && actions.contains(&ControlledAction::MoveRight) {
-            return Direction::Up.get_direction_vec() + Direction::Right.get_direction_vec();
-        }
-
-        if actions.contains(&ControlledAction::MoveDown) && actions.contains(&ControlledAction::MoveLeft) {
-            return Direction::Down.get_direction_vec() + Direction::Left.get_direction_vec();
-        }
-
-        if actions.contains(&ControlledAction::MoveDown) && actions.contains(&ControlledAction::MoveRight) {
-            return Direction::Down.get_direction_vec() + Direction::Right.get_direction_vec();
-        }
-
-        if actions.contains(&ControlledAction::MoveUp) && actions.contains(&ControlledAction::MoveLeft) {
-            return Direction::Up.get_direction_vec() + Direction::Left.get_direction_vec();
-        }
-
-        if actions.contains(&ControlledAction::MoveUp) {
-            return Direction::Up.get_direction_vec();
-        }
+    /// Digital movement axis of the pressed actions, opposing actions cancel each other out.
+    pub fn axis_from_actions(actions: &HashSet<ControlledAction>) -> Vec2 {
+        let pressed = |action: ControlledAction| if actions.contains(&action) { 1.0 } else { 0.0 };
 
-        if actions.contains(&ControlledAction::MoveDown) {
-            return Direction::Down.get_direction_vec();
-        }
+        Vec2::new(
+            pressed(ControlledAction::MoveRight) - pressed(ControlledAction::MoveLeft),
+            pressed(ControlledAction::MoveUp) - pressed(ControlledAction::MoveDown),
+        )
+    }
 
-        if actions.contains(&ControlledAction::MoveLeft) {
-            return Direction::Left.get_direction_vec();
+    /// Quantizes an arbitrary vector to the nearest of the 8 directions,
+    /// meant for picking animation rows rather than for movement itself.
+    pub fn from_vec(vec: Vec2) -> Direction {
+        if vec.length_squared() <= f32::EPSILON {
+            return Direction::Zero;
         }
 
-        if actions.contains(&ControlledAction::MoveRight) {
-            return Direction::Right.get_direction_vec();
-        }
+        let sector = (vec.to_angle() / FRAC_PI_4).round() as i32;
 
-        Direction::Zero.get_direction_vec()
+        Self::SECTORS[sector.rem_euclid(Self::SECTORS.len() as i32) as usize]
     }
 
     pub fn is_neg_x_axes(&self) -> bool {
@@ -199,21 +213,74 @@ impl Direction {
     }
 }
 
-pub fn update_position(
-    mut event_reader: EventReader<MoveEvent>,
-    mut transform_query: Query<&mut Transform>,
-    time: Res<Time>,
+/// Character controller tuned for a top-down world: there is no ground to snap to,
+/// so only sliding along obstacles is kept from the rapier defaults.
+pub fn top_down_controller() -> KinematicCharacterController {
+    KinematicCharacterController {
+        snap_to_ground: None,
+        autostep: None,
+        ..default()
+    }
+}
+
+pub fn apply_move_intent(
+    mut move_event_reader: EventReader<MoveEvent>,
+    mut move_end_event_reader: EventReader<MoveEndEvent>,
+    mut intent_query: Query<&mut MoveIntent>,
 ) {
-    for mut move_event in event_reader.read() {
-        let (mut transform) = transform_query.get_mut(move_event.entity).unwrap();
+    for move_event in move_event_reader.read() {
+        let Ok(mut intent) = intent_query.get_mut(move_event.entity) else {
+            warn!("Entity {:?} has no movement components", move_event.entity);
+            continue;
+        };
         info!("Get event: {:?}", &move_event);
 
-        let mut direction = move_event.direction.get_direction_vec();
+        intent.direction = move_event.direction.clamp_length_max(1.0);
+        intent.is_running = move_event.is_running;
+    }
 
-        if direction.length() > 0.0 {
-            direction = direction.normalize()
+    for move_end_event in move_end_event_reader.read() {
+        if let Ok(mut intent) = intent_query.get_mut(move_end_event.entity) {
+            *intent = MoveIntent::default();
+        }
+    }
+}
+
+pub fn integrate_velocity(
+    mut query: Query<(&mut MoveVelocity, &MoveIntent, &MovementStats)>,
+    time: Res<Time>,
+) {
+    let delta = time.delta_seconds();
+
+    for (mut velocity, intent, stats) in query.iter_mut() {
+        let max_speed = if intent.is_running {
+            stats.run_max_speed
+        } else {
+            stats.max_speed
+        };
+        let target = intent.direction * max_speed;
+
+        // Speeding up follows the acceleration curve, everything that slows the entity down
+        // (releasing keys, dropping from run to walk) follows friction.
+        let rate = if target != Vec2::ZERO && target.length() >= velocity.length() {
+            stats.acceleration
+        } else {
+            stats.friction
+        };
+
+        velocity.0 = velocity.move_towards(target, rate * delta);
+    }
+}
+
+pub fn update_position(
+    mut query: Query<(&MoveVelocity, &mut KinematicCharacterController)>,
+    time: Res<Time>,
+) {
+    for (velocity, mut controller) in query.iter_mut() {
+        if velocity.0 == Vec2::ZERO {
+            continue;
         }
 
-        transform.translation += direction * move_event.speed * move_event.acceleration * time.delta_seconds();
+        controller.translation = Some(velocity.0 * time.delta_seconds());
     }
 }
@@ -1,18 +1,21 @@
 use bevy::prelude::{
     default, in_state, info, App, AssetServer, Commands, Component, Entity, EventWriter,
-    IntoSystemConfigs, OnEnter, OnExit, Plugin, Query, Res, ResMut, Resource, SpriteBundle, Time,
-    Timer, TimerMode, Transform, UVec2, Update, Vec3, With,
+    IntoSystemConfigs, OnEnter, Plugin, Query, Res, ResMut, Resource, SpriteBundle, StateScoped,
+    Time, Timer, TimerMode, Transform, Update, Vec2, Vec3, With,
 };
 use bevy_rapier2d::geometry::Collider;
-use bevy_rapier2d::prelude::{GravityScale, KinematicCharacterController, RigidBody};
+use bevy_rapier2d::prelude::RigidBody;
 use rand::random;
 
+use crate::audio::audio::Footsteps;
 use crate::game::game::GameState;
-use crate::game::movement::movement::{Direction, MoveEvent};
-use crate::{AppState, WINDOW_HEIGHT, WINDOW_WIDTH};
+use crate::game::health::health::Health;
+use crate::game::movement::movement::{Direction, MoveEvent, MovementBundle, MovementStats, top_down_controller};
+use crate::ysort::ysort::YSort;
+use crate::video::video::INTERNAL_RESOLUTION;
+use crate::AppState;
 
-const WARRIOR_WIDTH: u32 = 128;
-const WARRIOR_HEIGHT: u32 = 128;
+const WARRIOR_SPEED: f32 = 50.0;
 
 pub struct NpcPlugin;
 
@@ -21,8 +24,6 @@ impl Plugin for NpcPlugin {
         app.init_resource::<WarriorDirection>()
             .init_resource::<WarriorDirectionTimer>()
             .add_systems(OnEnter(AppState::Game), spawn_warriors)
-            .add_systems(OnExit(AppState::Game), despawn_warriors)
-            .add_systems(OnEnter(AppState::MainMenu), despawn_warriors)
             .add_systems(
                 Update,
                 (
@@ -52,29 +53,35 @@ impl Default for WarriorDirectionTimer {
 }
 
 pub fn spawn_warriors(mut commands: Commands, asset_server: Res<AssetServer>) {
+    // Somewhere on the first screen around the world origin.
+    let position = (Vec2::new(random::<f32>(), random::<f32>()) - 0.5)
+        * INTERNAL_RESOLUTION.as_vec2();
+    spawn_warrior(&mut commands, &asset_server, position, Health::default());
+}
+
+pub fn spawn_warrior(
+    commands: &mut Commands,
+    asset_server: &Res<AssetServer>,
+    position: Vec2,
+    health: Health,
+) -> Entity {
     commands.spawn((
         SpriteBundle {
-            transform: Transform::from_xyz(
-                WINDOW_WIDTH * random::<f32>(),
-                WINDOW_HEIGHT * random::<f32>(),
-                1.0,
-            )
-            .with_scale(Vec3::new(1.5, 1.5, 0.0)),
+            transform: Transform::from_translation(position.extend(1.0))
+                .with_scale(Vec3::new(1.5, 1.5, 0.0)),
             texture: asset_server.load("sprites/characters/fighter/idle-walk.png"),
             ..default()
         },
         Collider::cuboid((60 / 2) as f32, (55) as f32),
-        RigidBody::Dynamic,
-        GravityScale(0.0),
-        //KinematicCharacterController::default(),
+        RigidBody::KinematicPositionBased,
+        top_down_controller(),
+        MovementBundle::new(MovementStats::new(WARRIOR_SPEED, WARRIOR_SPEED * 2.0, 400.0, 400.0)),
+        Footsteps::default(),
+        YSort::new(-55.0 * 1.5),
+        health,
         Warrior {},
-    ));
-}
-
-pub fn despawn_warriors(mut commands: Commands, query: Query<Entity, With<Warrior>>) {
-    if let Ok(entity) = query.get_single() {
-        commands.entity(entity).despawn()
-    }
+        StateScoped(AppState::Game),
+    )).id()
 }
 
 pub fn change_warrior_direction(
@@ -91,7 +98,7 @@ pub fn change_warrior_direction(
 }
 
 pub fn warrior_movement(
-    mut query: Query<Entity, With<Warrior>>,
+    query: Query<Entity, With<Warrior>>,
     mut move_event_writer: EventWriter<MoveEvent>,
     warrior_direction: Res<WarriorDirection>,
 ) {
@@ -100,6 +107,6 @@ pub fn warrior_movement(
         warrior_direction.direction
     );
     for entity in query.iter() {
-        move_event_writer.send(MoveEvent::new(&entity, warrior_direction.direction, 50.0, 1.0));
+        move_event_writer.send(MoveEvent::new(&entity, warrior_direction.direction.get_direction_vec().truncate(), false));
     }
 }
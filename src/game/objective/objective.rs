@@ -0,0 +1,48 @@
+use bevy::prelude::{
+    in_state, state_changed, App, DetectChangesMut, IntoSystemConfigs, OnExit, Plugin, Res, ResMut,
+    Resource, State, Update,
+};
+
+use crate::game::location::location::{CurrentLocation, LocationRegistry};
+use crate::AppState;
+
+pub struct ObjectivePlugin;
+
+impl Plugin for ObjectivePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Objective>()
+            .add_systems(OnExit(AppState::Game), clear_objective)
+            .add_systems(
+                Update,
+                set_location_objective
+                    .run_if(in_state(AppState::Game))
+                    .run_if(state_changed::<CurrentLocation>),
+            );
+    }
+}
+
+/// What the player is asked to do next, empty when there is nothing to do.
+#[derive(Resource, Debug, Clone, Default, PartialEq)]
+pub struct Objective {
+    pub description: String,
+}
+
+/// Each location comes with an objective of its own.
+pub fn set_location_objective(
+    registry: Res<LocationRegistry>,
+    current_location: Res<State<CurrentLocation>>,
+    mut objective: ResMut<Objective>,
+) {
+    let description = registry
+        .get(current_location.get().0)
+        .and_then(|definition| definition.objective)
+        .unwrap_or_default();
+
+    objective.set_if_neq(Objective {
+        description: description.to_string(),
+    });
+}
+
+pub fn clear_objective(mut objective: ResMut<Objective>) {
+    objective.set_if_neq(Objective::default());
+}
@@ -1,38 +1,38 @@
-use std::cmp::PartialEq;
-use std::collections::{HashMap, HashSet};
-use std::time::Duration;
-use std::vec;
+use std::collections::HashMap;
 
-use bevy::asset::ErasedAssetLoader;
-use bevy::audio::CpalSample;
 use bevy::prelude::{
-    App, AssetEvent, Assets, AssetServer, ButtonInput, Camera, Commands, Component, default, Entity,
-    EventReader, EventWriter, Handle, Image, in_state, info, IntoSystemConfigs, KeyCode,
-    NextState, OnEnter, OnExit, Plugin, Query, Res, ResMut, Resource, Sprite, SpriteBundle,
-    TextureAtlas, TextureAtlasBuilder, TextureAtlasLayout, Time, Timer, TimerMode, Transform,
-    TransformBundle, Update, UVec2, Vec3, With, Without,
+    App, AssetEvent, Assets, AssetServer, Commands, Component, default, Entity,
+    EventReader, EventWriter, Handle, Image, in_state, info, IntoSystemConfigs, Local, NextState,
+    OnEnter, Plugin, Query, Res, ResMut, Resource, SpriteBundle, StateScoped, TextureAtlas, TextureAtlasLayout,
+    TimerMode, Transform, Update, UVec2, Vec3, With,
 };
-use bevy::prelude::KeyCode::{
-    ArrowDown, ArrowLeft, ArrowRight, ArrowUp, KeyA, KeyD, KeyF, KeyS, KeyW, ShiftLeft, ShiftRight,
-};
-use bevy_rapier2d::dynamics::GravityScale;
 use bevy_rapier2d::prelude::{
-    Collider, ImpulseJoint, KinematicCharacterController, NoUserData, RapierDebugRenderPlugin,
-    RapierPhysicsPlugin, RigidBody,
+    Collider, NoUserData, RapierDebugRenderPlugin, RapierPhysicsPlugin, RigidBody,
 };
 
-use crate::{AppState, WINDOW_HEIGHT, WINDOW_WIDTH};
-use crate::animation::animation::{animate_clip, AnimationClip, AnimationClipResource, AnimationIndices, AnimationLibrary, AnimationResource, AnimationState, change_animation_clip, ClipChangeEvent, PepaAnimationPlugin};
-use crate::game::controls::controls::{ActionEndEvent, ActionEvent, Actions, ControlledAction, Controls};
+use crate::AppState;
+use crate::audio::audio::{Footsteps, PlaySfxEvent, SfxId};
+use crate::animation::animation::{animate_clip, AnimationClip, AnimationClipResource, AnimationIndices, AnimationLibrary, AnimationResource, AnimationState, change_animation_clip, ClipChangeEvent};
+use crate::game::camera::camera::CameraTarget;
+use crate::game::controls::controls::{ActionEvent, Controls};
 use crate::game::game::GameState;
-use crate::game::movement::movement::{Direction, MoveEndEvent, MoveEvent};
-
-const STARTING_TRANSLATION: Vec3 = Vec3::new(WINDOW_WIDTH / 2.0, WINDOW_HEIGHT / 2.0, 1.0);
+use crate::game::health::health::Health;
+use crate::game::inventory::inventory::Inventory;
+use crate::game::stamina::stamina::Stamina;
+use crate::ysort::ysort::YSort;
+use crate::game::movement::movement::{Direction, MoveEndEvent, MoveEvent, MovementBundle, MovementStats, top_down_controller};
+
+/// The player starts at the world origin until the location puts it on a spawn point.
+const PLAYER_Z: f32 = 1.0;
 const PLAYER_SPEED: f32 = 200.0;
-const PLAYER_SIZE: f32 = 64.0;
+const PLAYER_RUN_SPEED: f32 = 400.0;
+const PLAYER_ACCELERATION: f32 = 1600.0;
+const PLAYER_FRICTION: f32 = 1200.0;
+const PLAYER_ATTACK_SFX: SfxId = SfxId::Gunshot;
 
 const RAW_PLAYER_INITIAL_WIDTH: u32 = 52;
 const RAW_PLAYER_INITIAL_HEIGHT: u32 = 52;
+const PLAYER_SCALE: f32 = 5.0;
 
 pub struct PlayerPlugin;
 
@@ -52,12 +52,11 @@ impl Plugin for PlayerPlugin {
             .add_systems(OnEnter(AppState::Game), (spawn_player,).chain())
             .add_plugins(RapierPhysicsPlugin::<NoUserData>::pixels_per_meter(100.0))
             .add_plugins(RapierDebugRenderPlugin::default())
-            .add_systems(OnEnter(AppState::MainMenu), despawn_player)
             .add_systems(
                 Update,
                 (
                     player_movement,
-                    stick_camera_to_player,
+                    play_attack_sound,
                     //TODO: move animate to animation plugin
                     animate_clip,
                     change_animation_clip
@@ -170,7 +169,7 @@ pub fn check_assets_loading(
     mut player_animation_assets: ResMut<PlayerAssets>,
 ) {
     for event in assets_event.read() {
-        for mut asset in player_animation_assets.assets.iter_mut() {
+        for asset in player_animation_assets.assets.iter_mut() {
             if event.is_loaded_with_dependencies(asset.texture.id()) {
                 asset.is_loaded = true
             }
@@ -188,7 +187,7 @@ pub fn spawn_player(mut commands: Commands, animation_library: Res<AnimationLibr
     let (clip, resource) = animation_library.clips.get(&(AnimationState::Idle, Direction::Zero)).unwrap();
     commands.spawn((
         SpriteBundle {
-            transform: Transform::from_translation(STARTING_TRANSLATION).with_scale(Vec3::new(5.0, 5.0, 1.0)),
+            transform: Transform::from_xyz(0.0, 0.0, PLAYER_Z).with_scale(Vec3::new(PLAYER_SCALE, PLAYER_SCALE, 1.0)),
             texture: resource.texture.clone(),
             ..default()
         },
@@ -197,34 +196,34 @@ pub fn spawn_player(mut commands: Commands, animation_library: Res<AnimationLibr
             layout: resource.atlas_layout.clone(),
             index: clip.indices.first,
         },
-        Controls {
-            //TODO: move to resources
-            controls_map: HashMap::from([
-                (KeyW, ControlledAction::MoveUp),
-                (KeyA, ControlledAction::MoveLeft),
-                (KeyS, ControlledAction::MoveDown),
-                (KeyD, ControlledAction::MoveRight),
-                (ArrowUp, ControlledAction::MoveUp),
-                (ArrowLeft, ControlledAction::MoveLeft),
-                (ArrowDown, ControlledAction::MoveDown),
-                (ArrowRight, ControlledAction::MoveRight),
-                (ShiftLeft, ControlledAction::Run),
-                (KeyF, ControlledAction::Attack),
-            ]),
-        },
+        Controls::default(),
         Collider::cuboid(
             (RAW_PLAYER_INITIAL_WIDTH / 4) as f32,
             (RAW_PLAYER_INITIAL_HEIGHT / 4) as f32,
         ),
         RigidBody::KinematicPositionBased,
+        top_down_controller(),
+        MovementBundle::new(MovementStats::new(
+            PLAYER_SPEED,
+            PLAYER_RUN_SPEED,
+            PLAYER_ACCELERATION,
+            PLAYER_FRICTION,
+        )),
+        Footsteps::default(),
+        // Sorted by the bottom of the collider, where the feet are.
+        YSort::new(-((RAW_PLAYER_INITIAL_HEIGHT / 4) as f32) * PLAYER_SCALE),
+        (Health::default(), Stamina::default(), Inventory::default()),
+        CameraTarget,
         Player {},
+        StateScoped(AppState::Game),
     ));
 }
 
 pub fn player_movement(
-    mut query: Query<Entity, With<Player>>,
+    query: Query<Entity, With<Player>>,
     mut event_reader: EventReader<ActionEvent>,
     mut move_event_writer: EventWriter<MoveEvent>,
+    mut move_end_event_writer: EventWriter<MoveEndEvent>,
     mut clip_event_writer: EventWriter<ClipChangeEvent>,
 ) {
     let mut prev_event = None;
@@ -233,73 +232,64 @@ pub fn player_movement(
         info!("Get event: {:?}", event);
 
         if prev_event == Some(event) {
-            return;
-        }
-
-        if event.is_idle() {
-            clip_event_writer.send(ClipChangeEvent::new(&player_entity, AnimationState::Idle, Direction::Zero));
-            prev_event = Some(event);
-            return;
+            continue;
         }
+        prev_event = Some(event);
 
         if event.is_attack() {
             let attack_event = ClipChangeEvent::new(&player_entity, AnimationState::Attack, Direction::Zero);
-            let move_event = MoveEvent::new(&player_entity, Direction::Zero, 1.0, PLAYER_SPEED);
-            info!("Sending Move event: {:?} and ClipChange event: {:?}", &move_event, &attack_event);
+            info!("Sending MoveEnd event and ClipChange event: {:?}", &attack_event);
 
-            move_event_writer.send(move_event);
+            move_end_event_writer.send(MoveEndEvent::new(&player_entity));
             clip_event_writer.send(attack_event);
-            prev_event = Some(event);
-            return;
+            continue;
         }
 
-        if event.contains_move() {
-            let direction = Direction::from_actions(event.actions.clone());
-
-            if event.contains_attack() {
-                let attack_event = ClipChangeEvent::new(&player_entity, AnimationState::Attack, direction);
-                let move_event = MoveEvent::new(&player_entity, direction, 1.0, PLAYER_SPEED);
-                info!("Sending Move event: {:?} and ClipChange event: {:?}", &move_event, &attack_event);
-
-                move_event_writer.send(move_event);
-                clip_event_writer.send(attack_event);
-                prev_event = Some(event);
-                return;
-            }
+        // Covers released keys as well as opposing keys cancelling each other out.
+        if !event.is_moving() {
+            clip_event_writer.send(ClipChangeEvent::new(&player_entity, AnimationState::Idle, Direction::Zero));
+            move_end_event_writer.send(MoveEndEvent::new(&player_entity));
+            continue;
+        }
 
-            let (animation_state, speed_multiplier) = if event.contains_running() {
-                (AnimationState::Run, 2.0)
-            } else {
-                (AnimationState::Run, 1.0)
-            };
+        let direction = Direction::from_vec(event.axis);
 
-            let clip_event = ClipChangeEvent::new(&player_entity, animation_state, direction);
-            let move_event = MoveEvent::new(&player_entity, direction, speed_multiplier, PLAYER_SPEED);
+        if event.contains_attack() {
+            let attack_event = ClipChangeEvent::new(&player_entity, AnimationState::Attack, direction);
+            let move_event = MoveEvent::new(&player_entity, event.axis, false);
+            info!("Sending Move event: {:?} and ClipChange event: {:?}", &move_event, &attack_event);
 
-            info!("Sending Move event: {:?} and ClipChange event: {:?}", &move_event, &clip_event);
             move_event_writer.send(move_event);
-            clip_event_writer.send(clip_event);
+            clip_event_writer.send(attack_event);
+            continue;
         }
 
-        prev_event = Some(event);
+        let is_running = event.contains_running();
+        let clip_event = ClipChangeEvent::new(&player_entity, AnimationState::Run, direction);
+        let move_event = MoveEvent::new(&player_entity, event.axis, is_running);
+
+        info!("Sending Move event: {:?} and ClipChange event: {:?}", &move_event, &clip_event);
+        move_event_writer.send(move_event);
+        clip_event_writer.send(clip_event);
     }
 }
 
-pub fn stick_camera_to_player(
-    mut camera_query: Query<&mut Transform, With<Camera>>,
-    player_query: Query<&Transform, (With<Player>, Without<Camera>)>,
-    time: Res<Time>,
+/// Actions are sent every frame, the shot only sounds when the attack starts.
+pub fn play_attack_sound(
+    query: Query<Entity, With<Player>>,
+    mut event_reader: EventReader<ActionEvent>,
+    mut play_sfx_writer: EventWriter<PlaySfxEvent>,
+    mut was_attacking: Local<bool>,
 ) {
-    let mut camera_transform = camera_query.single_mut();
-    let player_transform = player_query.single();
-
-    camera_transform.translation = camera_transform
-        .translation
-        .lerp(player_transform.translation, 2.0 * time.delta_seconds());
-}
+    let Ok(player_entity) = query.get_single() else {
+        return;
+    };
 
-pub fn despawn_player(mut commands: Commands, player_query: Query<Entity, With<Player>>) {
-    if let Ok(player_entity) = player_query.get_single() {
-        commands.entity(player_entity).despawn()
+    for event in event_reader.read() {
+        let is_attacking = event.contains_attack();
+        if is_attacking && !*was_attacking {
+            play_sfx_writer.send(PlaySfxEvent::from_entity(PLAYER_ATTACK_SFX, player_entity));
+        }
+        *was_attacking = is_attacking;
     }
 }
@@ -0,0 +1,101 @@
+use bevy::prelude::{
+    in_state, App, Component, IntoSystemConfigs, Plugin, Query, Res, Time, Update, Vec2,
+};
+
+use crate::game::game::GameState;
+use crate::game::movement::movement::{apply_move_intent, integrate_velocity, MoveIntent};
+use crate::AppState;
+
+pub const DEFAULT_MAX_STAMINA: f32 = 100.0;
+/// Stamina used up per second of running.
+const DEFAULT_DRAIN_RATE: f32 = 25.0;
+/// Stamina regained per second once the entity has rested for a moment.
+const DEFAULT_REGEN_RATE: f32 = 20.0;
+/// Seconds without running before stamina starts to come back.
+const DEFAULT_REGEN_DELAY: f32 = 1.0;
+
+pub struct StaminaPlugin;
+
+impl Plugin for StaminaPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            use_stamina
+                .after(apply_move_intent)
+                .before(integrate_velocity)
+                .run_if(in_state(AppState::Game))
+                .run_if(in_state(GameState::Running)),
+        );
+    }
+}
+
+/// Running drains stamina, once it is gone the entity drops back to walking until the
+/// run key is pressed again.
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct Stamina {
+    pub current: f32,
+    pub max: f32,
+    pub drain_rate: f32,
+    pub regen_rate: f32,
+    pub regen_delay: f32,
+    /// Seconds since the entity last ran.
+    rested: f32,
+}
+
+impl Default for Stamina {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_STAMINA)
+    }
+}
+
+impl Stamina {
+    pub fn new(max: f32) -> Self {
+        Self {
+            current: max,
+            max,
+            drain_rate: DEFAULT_DRAIN_RATE,
+            regen_rate: DEFAULT_REGEN_RATE,
+            regen_delay: DEFAULT_REGEN_DELAY,
+            rested: 0.0,
+        }
+    }
+
+    pub fn is_exhausted(&self) -> bool {
+        self.current <= 0.0
+    }
+
+    /// Share of the maximum stamina left, from 0 to 1.
+    pub fn fraction(&self) -> f32 {
+        if self.max > 0.0 {
+            self.current / self.max
+        } else {
+            0.0
+        }
+    }
+}
+
+pub fn use_stamina(time: Res<Time>, mut query: Query<(&mut Stamina, &mut MoveIntent)>) {
+    let delta = time.delta_seconds();
+
+    for (mut stamina, mut intent) in query.iter_mut() {
+        let running = intent.is_running && intent.direction != Vec2::ZERO;
+
+        if running {
+            stamina.rested = 0.0;
+            stamina.current = (stamina.current - stamina.drain_rate * delta).max(0.0);
+            if stamina.is_exhausted() {
+                intent.is_running = false;
+            }
+            continue;
+        }
+
+        // Only writes mark the stamina as changed, a full bar is left alone.
+        if stamina.current >= stamina.max {
+            continue;
+        }
+        stamina.rested += delta;
+        if stamina.rested >= stamina.regen_delay {
+            stamina.current = (stamina.current + stamina.regen_rate * delta).min(stamina.max);
+        }
+    }
+}
@@ -1,9 +1,8 @@
+use bevy::audio::Volume;
 use bevy::prelude::*;
 
-use crate::{AppState, WINDOW_HEIGHT, WINDOW_WIDTH};
-
-const LOGO_WIDTH: f32 = 566.0;
-const LOGO_HEIGHT: f32 = 68.0;
+use crate::audio::audio::{AudioChannel, AudioVolumes};
+use crate::AppState;
 
 const LOGO_DURATION: f32 = 10.0;
 
@@ -29,33 +28,35 @@ impl Default for LogoTimer {
     }
 }
 
-pub fn show_logo(mut commands: Commands, asset_server: Res<AssetServer>) {
+pub fn show_logo(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    volumes: Res<AudioVolumes>,
+) {
     commands.spawn((
         SpriteBundle {
-            transform: Transform::from_xyz(WINDOW_WIDTH / 2.0, WINDOW_HEIGHT / 2.0, 1.0),
+            transform: Transform::from_xyz(0.0, 0.0, 1.0),
             texture: asset_server.load("images/pipisenok-studios-logo.png"),
             ..default()
         },
         AudioBundle {
             source: asset_server.load("audio/pipisenok-logo.wav"),
-            settings: PlaybackSettings::ONCE,
+            settings: PlaybackSettings::ONCE
+                .with_volume(Volume::new(volumes.effective(AudioChannel::Master))),
         },
+        AudioChannel::Master,
         Logo {},
+        StateScoped(AppState::Logo),
     ));
 }
 
 pub fn handle_logo_end(
     mut logo_timer: ResMut<LogoTimer>,
-    mut commands: Commands,
-    query: Query<Entity, With<Logo>>,
     mut next_state: ResMut<NextState<AppState>>,
     time: Res<Time>,
 ) {
     logo_timer.0.tick(time.delta());
     if logo_timer.0.finished() {
         next_state.set(AppState::Loading);
-        let entity = query.single();
-
-        commands.entity(entity).despawn();
     }
 }
@@ -1,37 +1,71 @@
+// Every module keeps its code in a file named after it, e.g. `audio/audio.rs`.
+#![allow(clippy::module_inception)]
+
 use bevy::prelude::*;
-use bevy::window::{WindowMode, WindowResolution};
 
+use crate::audio::audio::PepaAudioPlugin;
+use crate::game::controls::controls::{capture_rebind, rebind_in_progress};
 use crate::game::game::GamePlugin;
 use crate::game::location::location::LocationPlugin;
 use crate::logo::logo::LogoPlugin;
+use crate::parallax::parallax::ParallaxPlugin;
+use crate::save::save::SavePlugin;
+use crate::tilemap::tilemap::TileMapPlugin;
 use crate::systems::*;
+use crate::ui::settings::SettingsState;
 use crate::ui::UiPlugin;
+use crate::video::video::{VideoPlugin, VideoSettings};
+use crate::ysort::ysort::YSortPlugin;
 
 pub mod animation;
+pub mod audio;
 pub mod game;
 pub mod logo;
+pub mod parallax;
+pub mod save;
+pub mod settings;
 pub mod systems;
+pub mod tilemap;
 pub mod ui;
-
-const WINDOW_WIDTH: f32 = 1920.0;
-const WINDOW_HEIGHT: f32 = 1080.0;
+pub mod video;
+pub mod ysort;
 
 fn main() {
+    let video_settings = VideoSettings::load();
+
     App::new()
         .add_plugins(
             DefaultPlugins
-                .set(get_window_settings())
+                .set(video_settings.window_plugin())
                 .set(ImagePlugin::default_nearest()),
         )
-        .add_plugins((UiPlugin, GamePlugin, LocationPlugin, LogoPlugin))
+        .insert_resource(video_settings)
+        // Plugins hang their own sub-states and cleanup off the app state, so it goes first.
         .init_state::<AppState>()
+        .enable_state_scoped_entities::<AppState>()
+        .add_plugins((
+            UiPlugin,
+            GamePlugin,
+            LocationPlugin,
+            LogoPlugin,
+            ParallaxPlugin,
+            PepaAudioPlugin,
+            SavePlugin,
+            TileMapPlugin,
+            VideoPlugin,
+            YSortPlugin,
+        ))
         .add_systems(
             Update,
             (
                 exit_on_escape,
-                transition_to_game_state,
-                transition_to_main_menu_state,
-            ),
+                // The shortcuts wait for the settings to close, like the pause toggle.
+                (transition_to_game_state, transition_to_main_menu_state)
+                    .run_if(in_state(SettingsState::Closed)),
+            )
+                // Keys pressed while rebinding belong to the rebind, Escape cancels it.
+                .run_if(not(rebind_in_progress))
+                .after(capture_rebind),
         )
         .run();
 }
@@ -45,16 +79,3 @@ pub enum AppState {
     Game,
     GameOver,
 }
-
-fn get_window_settings() -> WindowPlugin {
-    WindowPlugin {
-        primary_window: Some(Window {
-            resolution: WindowResolution::new(WINDOW_WIDTH, WINDOW_HEIGHT),
-            title: "Last of Pipisenok".to_string(),
-            resizable: false,
-            mode: WindowMode::Fullscreen,
-            ..Default::default()
-        }),
-        ..Default::default()
-    }
-}
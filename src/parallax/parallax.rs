@@ -0,0 +1,123 @@
+use bevy::prelude::{
+    default, App, Assets, Bundle, Camera, Component, Handle, Image, IntoSystemConfigs, Plugin,
+    PostUpdate, Query, Res, Sprite, SpriteBundle, Time, Transform, TransformSystem, Update, Vec2,
+    With, Without,
+};
+use bevy::sprite::ImageScaleMode;
+
+/// Copies of the image drawn side by side, enough to cover the view between two wraps.
+const PARALLAX_TILES: f32 = 3.0;
+
+pub struct ParallaxPlugin;
+
+impl Plugin for ParallaxPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, size_parallax_layers).add_systems(
+            PostUpdate,
+            scroll_parallax_layers.before(TransformSystem::TransformPropagate),
+        );
+    }
+}
+
+/// Image repeated endlessly along x that scrolls at its own pace as the camera moves.
+#[derive(Component, Debug, Clone)]
+pub struct ParallaxLayer {
+    /// How fast the layer scrolls compared to the world: 1 moves with it, 0 stays on
+    /// screen like a far away sky, above 1 passes by in front of the world.
+    pub scroll_factor: Vec2,
+    /// Horizontal drift in pixels per second, e.g. for clouds or falling leaves.
+    pub auto_scroll: f32,
+    /// Where the layer is drawn while the camera is at the same point.
+    pub anchor: Vec2,
+    /// Width of one copy of the image in the world, known once the image has loaded.
+    tile_width: Option<f32>,
+    drifted: f32,
+}
+
+impl ParallaxLayer {
+    pub fn new(anchor: Vec2, scroll_factor: Vec2) -> Self {
+        Self {
+            scroll_factor,
+            auto_scroll: 0.0,
+            anchor,
+            tile_width: None,
+            drifted: 0.0,
+        }
+    }
+
+    pub fn with_auto_scroll(mut self, auto_scroll: f32) -> Self {
+        self.auto_scroll = auto_scroll;
+        self
+    }
+}
+
+#[derive(Bundle)]
+pub struct ParallaxBundle {
+    pub layer: ParallaxLayer,
+    pub sprite: SpriteBundle,
+    pub scale_mode: ImageScaleMode,
+}
+
+impl ParallaxBundle {
+    pub fn new(texture: Handle<Image>, layer: ParallaxLayer, z: f32) -> Self {
+        Self {
+            sprite: SpriteBundle {
+                texture,
+                transform: Transform::from_translation(layer.anchor.extend(z)),
+                ..default()
+            },
+            layer,
+            scale_mode: ImageScaleMode::Tiled {
+                tile_x: true,
+                tile_y: false,
+                stretch_value: 1.0,
+            },
+        }
+    }
+}
+
+/// Stretches each new layer over several copies of its image once the size is known.
+pub fn size_parallax_layers(
+    images: Res<Assets<Image>>,
+    mut layer_query: Query<(&mut ParallaxLayer, &mut Sprite, &Handle<Image>, &Transform)>,
+) {
+    for (mut layer, mut sprite, texture, transform) in layer_query.iter_mut() {
+        if layer.tile_width.is_some() {
+            continue;
+        }
+        let Some(image) = images.get(texture) else {
+            continue;
+        };
+
+        let size = image.size().as_vec2();
+        sprite.custom_size = Some(Vec2::new(size.x * PARALLAX_TILES, size.y));
+        layer.tile_width = Some(size.x * transform.scale.x);
+    }
+}
+
+/// Runs after the camera has moved for the frame, so the layers never lag behind it.
+pub fn scroll_parallax_layers(
+    time: Res<Time>,
+    camera_query: Query<&Transform, With<Camera>>,
+    mut layer_query: Query<(&mut ParallaxLayer, &mut Transform), Without<Camera>>,
+) {
+    let Ok(camera_transform) = camera_query.get_single() else {
+        return;
+    };
+    let camera = camera_transform.translation.truncate();
+
+    for (mut layer, mut transform) in layer_query.iter_mut() {
+        let Some(tile_width) = layer.tile_width else {
+            continue;
+        };
+        layer.drifted = (layer.drifted + layer.auto_scroll * time.delta_seconds()) % tile_width;
+
+        let follow = Vec2::ONE - layer.scroll_factor;
+        let mut position = layer.anchor + (camera - layer.anchor) * follow;
+        position.x += layer.drifted;
+        // Jumping by whole copies is invisible, it keeps the middle copy under the camera.
+        position.x += ((camera.x - position.x) / tile_width).round() * tile_width;
+
+        transform.translation = position.extend(transform.translation.z);
+    }
+}
@@ -0,0 +1,377 @@
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::time::SystemTime;
+
+use bevy::prelude::{
+    in_state, info, on_event, resource_exists, warn, App, AppExit, Commands, Event, EventReader,
+    EventWriter, IntoSystemConfigs, Last, NextState, OnEnter, OnExit, Plugin, Query, Res, ResMut,
+    Resource, State, StateTransitionEvent, Transform, Update, Vec2, With,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::game::game::WorldFlags;
+use crate::game::health::health::Health;
+use crate::game::inventory::inventory::Inventory;
+use crate::game::location::location::{
+    CurrentLocation, LocationChangedEvent, LocationEntrance, LocationId, START_SPAWN,
+};
+use crate::game::player::player::Player;
+use crate::settings::settings::{write_ron, GAME_DIR};
+use crate::AppState;
+
+/// Bumped whenever [`SaveGame`] changes shape, older files are refused instead of misread.
+pub const SAVE_VERSION: u32 = 2;
+pub const SAVE_SLOTS: u8 = 3;
+
+pub struct SavePlugin;
+
+impl Plugin for SavePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ActiveSaveSlot>()
+            .add_event::<SaveGameEvent>()
+            .add_event::<LoadGameEvent>()
+            .add_event::<NewGameEvent>()
+            .add_systems(OnEnter(AppState::Game), start_save_session)
+            .add_systems(
+                OnExit(AppState::Game),
+                (save_on_exit, end_save_session).chain(),
+            )
+            // Runs after every system that may quit this frame, the app closes right after.
+            .add_systems(
+                Last,
+                save_on_app_exit
+                    .run_if(in_state(AppState::Game))
+                    .run_if(on_event::<AppExit>()),
+            )
+            .add_systems(
+                Update,
+                (
+                    start_new_game.run_if(on_event::<NewGameEvent>()),
+                    load_game.run_if(on_event::<LoadGameEvent>()),
+                    apply_pending_load
+                        .run_if(in_state(AppState::Game))
+                        .run_if(resource_exists::<PendingLoad>),
+                    autosave_on_location_change.run_if(on_event::<LocationChangedEvent>()),
+                    save_game
+                        .run_if(in_state(AppState::Game))
+                        .run_if(on_event::<SaveGameEvent>()),
+                )
+                    .chain(),
+            );
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct SaveSlot(pub u8);
+
+/// Slot the running game autosaves to, `None` when every slot was taken and the player
+/// didn't pick one, e.g. after jumping into the game with a shortcut.
+#[derive(Resource, Debug, Default)]
+pub struct ActiveSaveSlot(pub Option<SaveSlot>);
+
+#[derive(Event, Debug)]
+pub struct SaveGameEvent {
+    pub slot: SaveSlot,
+}
+
+impl SaveGameEvent {
+    pub fn new(slot: SaveSlot) -> Self {
+        Self { slot }
+    }
+}
+
+/// Starts a fresh game that saves to `slot`, replacing whatever was saved there.
+#[derive(Event, Debug)]
+pub struct NewGameEvent {
+    pub slot: SaveSlot,
+}
+
+impl NewGameEvent {
+    pub fn new(slot: SaveSlot) -> Self {
+        Self { slot }
+    }
+}
+
+#[derive(Event, Debug)]
+pub struct LoadGameEvent {
+    pub slot: SaveSlot,
+}
+
+impl LoadGameEvent {
+    pub fn new(slot: SaveSlot) -> Self {
+        Self { slot }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SaveGame {
+    pub version: u32,
+    pub player: PlayerSave,
+    pub flags: WorldFlags,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlayerSave {
+    pub position: Vec2,
+    pub health: Health,
+    pub inventory: Inventory,
+    pub location: LocationId,
+}
+
+/// Save read from disk, applied once the game session has spawned its entities.
+#[derive(Resource, Debug)]
+pub struct PendingLoad(pub SaveGame);
+
+pub fn save_path(slot: SaveSlot) -> Option<PathBuf> {
+    dirs::data_dir().map(|data_dir| {
+        data_dir
+            .join(GAME_DIR)
+            .join("saves")
+            .join(format!("slot_{}.ron", slot.0))
+    })
+}
+
+pub fn read_save(slot: SaveSlot) -> io::Result<SaveGame> {
+    let path = save_path(slot)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no data directory"))?;
+
+    let content = fs::read_to_string(path)?;
+    let save: SaveGame = ron::from_str(&content)
+        .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+
+    if save.version != SAVE_VERSION {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!(
+                "save version {} is not supported, expected {}",
+                save.version, SAVE_VERSION
+            ),
+        ));
+    }
+
+    Ok(save)
+}
+
+pub fn write_save(slot: SaveSlot, save: &SaveGame) -> io::Result<()> {
+    let path = save_path(slot)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no data directory"))?;
+
+    write_ron(&path, save)
+}
+
+fn saved_at(slot: SaveSlot) -> Option<SystemTime> {
+    let path = save_path(slot)?;
+    fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
+
+/// Slot written most recently, the one "Continue" picks up.
+pub fn latest_save_slot() -> Option<SaveSlot> {
+    (0..SAVE_SLOTS)
+        .map(SaveSlot)
+        .filter_map(|slot| saved_at(slot).map(|time| (slot, time)))
+        .max_by_key(|(_, time)| *time)
+        .map(|(slot, _)| slot)
+}
+
+pub fn is_slot_used(slot: SaveSlot) -> bool {
+    saved_at(slot).is_some()
+}
+
+pub fn free_slot() -> Option<SaveSlot> {
+    (0..SAVE_SLOTS)
+        .map(SaveSlot)
+        .find(|slot| !is_slot_used(*slot))
+}
+
+fn snapshot(
+    player_query: &Query<(&Transform, &Health, &Inventory), With<Player>>,
+    location: LocationId,
+    world_flags: &WorldFlags,
+) -> Option<SaveGame> {
+    let (transform, health, inventory) = player_query.get_single().ok()?;
+
+    Some(SaveGame {
+        version: SAVE_VERSION,
+        player: PlayerSave {
+            position: transform.translation.truncate(),
+            health: *health,
+            inventory: inventory.clone(),
+            location,
+        },
+        flags: world_flags.clone(),
+    })
+}
+
+fn write_snapshot(slot: SaveSlot, save: Option<SaveGame>) {
+    let Some(save) = save else {
+        warn!("Nothing to save, the player is not spawned");
+        return;
+    };
+
+    if let Err(error) = write_save(slot, &save) {
+        warn!("Failed to save the game to slot {}: {}", slot.0, error);
+    }
+}
+
+pub fn start_new_game(
+    mut new_game_reader: EventReader<NewGameEvent>,
+    mut active_slot: ResMut<ActiveSaveSlot>,
+    mut app_state_next_state: ResMut<NextState<AppState>>,
+) {
+    let Some(event) = new_game_reader.read().last() else {
+        return;
+    };
+
+    active_slot.0 = Some(event.slot);
+    app_state_next_state.set(AppState::Game);
+    info!("Game started");
+}
+
+/// Starts a fresh game on the start spawn point, unless the session was opened by loading
+/// a save. A game entered without picking a slot only takes an empty one, saves are never
+/// overwritten unasked.
+pub fn start_save_session(
+    mut commands: Commands,
+    pending_load: Option<Res<PendingLoad>>,
+    current_location: Res<State<CurrentLocation>>,
+    mut active_slot: ResMut<ActiveSaveSlot>,
+    mut world_flags: ResMut<WorldFlags>,
+) {
+    if pending_load.is_some() {
+        return;
+    }
+
+    *world_flags = WorldFlags::default();
+    commands.insert_resource(LocationEntrance {
+        location: current_location.get().0,
+        spawn: START_SPAWN.to_string(),
+    });
+    if active_slot.0.is_none() {
+        active_slot.0 = free_slot();
+    }
+
+    match active_slot.0 {
+        Some(slot) => info!("New game in slot {}", slot.0),
+        None => warn!("Every save slot is taken, this game will not be saved"),
+    }
+}
+
+/// The next session picks its slot again.
+pub fn end_save_session(mut active_slot: ResMut<ActiveSaveSlot>) {
+    active_slot.0 = None;
+}
+
+pub fn save_game(
+    mut save_reader: EventReader<SaveGameEvent>,
+    player_query: Query<(&Transform, &Health, &Inventory), With<Player>>,
+    current_location: Res<State<CurrentLocation>>,
+    world_flags: Res<WorldFlags>,
+) {
+    for event in save_reader.read() {
+        let location = current_location.get().0;
+        let save = snapshot(&player_query, location, &world_flags);
+        write_snapshot(event.slot, save);
+    }
+}
+
+/// Quitting skips [`OnExit`] of the game, so the progress is saved before the app closes.
+pub fn save_on_app_exit(
+    active_slot: Res<ActiveSaveSlot>,
+    player_query: Query<(&Transform, &Health, &Inventory), With<Player>>,
+    current_location: Res<State<CurrentLocation>>,
+    world_flags: Res<WorldFlags>,
+) {
+    let Some(slot) = active_slot.0 else {
+        return;
+    };
+
+    let save = snapshot(&player_query, current_location.get().0, &world_flags);
+    write_snapshot(slot, save);
+}
+
+/// The session entities are despawned right after, so this saves directly instead
+/// of going through [`SaveGameEvent`]. [`CurrentLocation`] is already gone by now,
+/// its last transition tells where the player was.
+pub fn save_on_exit(
+    active_slot: Res<ActiveSaveSlot>,
+    player_query: Query<(&Transform, &Health, &Inventory), With<Player>>,
+    mut location_transition_reader: EventReader<StateTransitionEvent<CurrentLocation>>,
+    world_flags: Res<WorldFlags>,
+) {
+    let Some(slot) = active_slot.0 else {
+        return;
+    };
+    let Some(location) = location_transition_reader
+        .read()
+        .last()
+        .and_then(|transition| transition.exited)
+    else {
+        warn!("Nothing to save, the location is unknown");
+        return;
+    };
+
+    let save = snapshot(&player_query, location.0, &world_flags);
+    write_snapshot(slot, save);
+}
+
+pub fn autosave_on_location_change(
+    mut location_reader: EventReader<LocationChangedEvent>,
+    active_slot: Res<ActiveSaveSlot>,
+    mut save_writer: EventWriter<SaveGameEvent>,
+) {
+    if location_reader.read().last().is_none() {
+        return;
+    }
+
+    if let Some(slot) = active_slot.0 {
+        save_writer.send(SaveGameEvent::new(slot));
+    }
+}
+
+pub fn load_game(
+    mut commands: Commands,
+    mut load_reader: EventReader<LoadGameEvent>,
+    mut active_slot: ResMut<ActiveSaveSlot>,
+    mut app_state_next_state: ResMut<NextState<AppState>>,
+    mut next_location: ResMut<NextState<CurrentLocation>>,
+) {
+    let Some(event) = load_reader.read().last() else {
+        return;
+    };
+
+    match read_save(event.slot) {
+        Ok(save) => {
+            info!("Loading slot {}", event.slot.0);
+            active_slot.0 = Some(event.slot);
+            // Picked up when the location state is created, so the session starts there.
+            next_location.set(CurrentLocation(save.player.location));
+            commands.insert_resource(PendingLoad(save));
+            app_state_next_state.set(AppState::Game);
+        }
+        Err(error) => warn!("Failed to load slot {}: {}", event.slot.0, error),
+    }
+}
+
+pub fn apply_pending_load(
+    mut commands: Commands,
+    pending_load: Res<PendingLoad>,
+    mut player_query: Query<(&mut Transform, &mut Health, &mut Inventory), With<Player>>,
+    mut world_flags: ResMut<WorldFlags>,
+) {
+    // The player spawns on entering the game, wait for it before applying the save.
+    let Ok((mut transform, mut health, mut inventory)) = player_query.get_single_mut() else {
+        return;
+    };
+    let save = &pending_load.0;
+
+    transform.translation = save.player.position.extend(transform.translation.z);
+    *health = save.player.health;
+    *inventory = save.player.inventory.clone();
+    *world_flags = save.flags.clone();
+
+    commands.remove_resource::<PendingLoad>();
+    info!("Save applied");
+}
@@ -0,0 +1,63 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use bevy::prelude::{info, warn};
+use ron::ser::PrettyConfig;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+/// Name of the game's folder inside the user config and data directories.
+pub const GAME_DIR: &str = "pipisenok-game";
+
+pub fn settings_path(file_name: &str) -> Option<PathBuf> {
+    dirs::config_dir().map(|config_dir| config_dir.join(GAME_DIR).join(file_name))
+}
+
+/// Reads a settings file from the user config directory, falling back to the defaults
+/// when the file does not exist yet or cannot be parsed.
+pub fn load_settings<T: DeserializeOwned + Default>(file_name: &str) -> T {
+    let Some(path) = settings_path(file_name) else {
+        warn!("No config directory found, using default {}", file_name);
+        return T::default();
+    };
+
+    let content = match fs::read_to_string(&path) {
+        Ok(content) => content,
+        Err(error) if error.kind() == io::ErrorKind::NotFound => {
+            info!("No {:?} found, using defaults", path);
+            return T::default();
+        }
+        Err(error) => {
+            warn!("Failed to read {:?}: {}, using defaults", path, error);
+            return T::default();
+        }
+    };
+
+    ron::from_str(&content).unwrap_or_else(|error| {
+        warn!("Failed to parse {:?}: {}, using defaults", path, error);
+        T::default()
+    })
+}
+
+pub fn save_settings<T: Serialize>(file_name: &str, settings: &T) -> io::Result<()> {
+    let path = settings_path(file_name)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no config directory"))?;
+
+    write_ron(&path, settings)
+}
+
+/// Writes `value` as pretty ron, creating the missing parent directories.
+pub fn write_ron<T: Serialize>(path: &Path, value: &T) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let content =
+        ron::ser::to_string_pretty(value, PrettyConfig::default()).map_err(io::Error::other)?;
+
+    fs::write(path, content)?;
+    info!("Saved {:?}", path);
+
+    Ok(())
+}
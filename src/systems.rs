@@ -1,22 +1,40 @@
 use bevy::prelude::*;
 
+use crate::game::controls::controls::RebindEvent;
 use crate::game::game::GameState;
+use crate::ui::settings::SettingsState;
 use crate::AppState;
 
+pub const START_GAME_KEY: KeyCode = KeyCode::KeyG;
+pub const MAIN_MENU_KEY: KeyCode = KeyCode::KeyM;
+
+/// Escape closes the overlay on top, the settings before the pause menu, and quits the
+/// game once none is open. It also cancels a rebind, a press that just did so does nothing else.
 pub fn exit_on_escape(
     keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut rebind_event_reader: EventReader<RebindEvent>,
+    settings_state: Res<State<SettingsState>>,
+    game_state: Option<Res<State<GameState>>>,
+    mut settings_next_state: ResMut<NextState<SettingsState>>,
+    mut game_next_state: ResMut<NextState<GameState>>,
     mut app_exit_events: ResMut<Events<AppExit>>,
 ) {
-    if keyboard_input.pressed(KeyCode::Escape) {
-        app_exit_events.send(AppExit::Success);
+    let rebind_cancelled = rebind_event_reader
+        .read()
+        .any(|rebind_event| matches!(rebind_event, RebindEvent::Cancelled { .. }));
+
+    if !keyboard_input.just_pressed(KeyCode::Escape) || rebind_cancelled {
+        return;
     }
-}
 
-pub fn play_background_sound(mut commands: Commands, asset_server: Res<AssetServer>) {
-    commands.spawn(AudioBundle {
-        source: asset_server.load("audio/avbe-night-adventures.wav"),
-        settings: PlaybackSettings::LOOP,
-    });
+    if *settings_state.get() != SettingsState::Closed {
+        settings_next_state.set(SettingsState::Closed);
+    } else if game_state.is_some_and(|game_state| *game_state.get() == GameState::Paused) {
+        game_next_state.set(GameState::Running);
+        info!("Game resumed")
+    } else {
+        app_exit_events.send(AppExit::Success);
+    }
 }
 
 pub fn transition_to_game_state(
@@ -24,7 +42,7 @@ pub fn transition_to_game_state(
     keyboard_input: Res<ButtonInput<KeyCode>>,
     mut next_state: ResMut<NextState<AppState>>,
 ) {
-    if keyboard_input.just_pressed(KeyCode::KeyG) {
+    if keyboard_input.just_pressed(START_GAME_KEY) {
         if *app_state.get() != AppState::Game {
             next_state.set(AppState::Game);
             info!("Game started")
@@ -36,12 +54,10 @@ pub fn transition_to_main_menu_state(
     app_state: Res<State<AppState>>,
     keyboard_input: Res<ButtonInput<KeyCode>>,
     mut next_app_state: ResMut<NextState<AppState>>,
-    mut next_game_state: ResMut<NextState<GameState>>,
 ) {
-    if keyboard_input.just_pressed(KeyCode::KeyM) {
+    if keyboard_input.just_pressed(MAIN_MENU_KEY) {
         if *app_state.get() != AppState::MainMenu {
             next_app_state.set(AppState::MainMenu);
-            next_game_state.set(GameState::Paused);
             info!("Returned to Main Menu")
         }
     }
@@ -0,0 +1,894 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::io;
+
+use bevy::asset::io::Reader;
+use bevy::asset::{AssetLoader, AsyncReadExt, LoadContext};
+use bevy::prelude::{
+    default, warn, App, Asset, AssetApp, Assets, BuildChildren, Bundle, Commands, Component,
+    Entity, Handle, Image, Plugin, Query, Rect, Res, SpatialBundle, Sprite, SpriteBundle,
+    Transform, TypePath, UVec2, Update, Vec2, Without,
+};
+use bevy::sprite::Anchor;
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::tilemap::tilemap::{Tile, TileLayer, TileMap, Tileset, LAYER_Z_STEP};
+
+const FLIPPED_HORIZONTALLY: u32 = 0x8000_0000;
+const FLIPPED_VERTICALLY: u32 = 0x4000_0000;
+const FLIP_FLAGS: u32 = 0xF000_0000;
+
+pub struct TiledMapPlugin;
+
+impl Plugin for TiledMapPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<TiledMap>()
+            .register_asset_loader(TiledMapLoader)
+            .add_systems(Update, spawn_tiled_maps);
+    }
+}
+
+/// Map made in the Tiled editor and saved as JSON (`.tmj`). Tilesets may be embedded
+/// or external `.tsj` files, tile layers must use the CSV encoding and finite maps.
+#[derive(Asset, TypePath, Debug)]
+pub struct TiledMap {
+    /// Size of the whole map in pixels, even when it has no tile layers.
+    pub size: Vec2,
+    pub tile_map: TileMap,
+    pub image_layers: Vec<MapImageLayer>,
+    pub objects: Vec<MapObject>,
+    pub properties: MapProperties,
+}
+
+impl TiledMap {
+    /// Area entities are kept in, relative to the top left corner of the map: the rect
+    /// of the `bounds` object when the map has one, the whole map otherwise.
+    pub fn bounds(&self) -> Rect {
+        self.objects
+            .iter()
+            .find_map(|object| match object.shape {
+                MapShape::Rect(size) if object.kind == "bounds" => {
+                    Some(Rect::from_center_size(object.position, size))
+                }
+                _ => None,
+            })
+            .unwrap_or_else(|| Rect::from_corners(Vec2::ZERO, Vec2::new(self.size.x, -self.size.y)))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct MapImageLayer {
+    pub name: String,
+    pub image: Handle<Image>,
+    /// Offset of the image's top left corner from the map's, y pointing up.
+    pub offset: Vec2,
+    pub z: f32,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum MapProperty {
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    String(String),
+}
+
+/// Custom properties set in the editor on a map or an object.
+#[derive(Component, Debug, Clone, Default)]
+pub struct MapProperties(pub HashMap<String, MapProperty>);
+
+impl MapProperties {
+    pub fn get_str(&self, name: &str) -> Option<&str> {
+        match self.0.get(name) {
+            Some(MapProperty::String(value)) => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn get_bool(&self, name: &str) -> Option<bool> {
+        match self.0.get(name) {
+            Some(MapProperty::Bool(value)) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn get_int(&self, name: &str) -> Option<i64> {
+        match self.0.get(name) {
+            Some(MapProperty::Int(value)) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn get_float(&self, name: &str) -> Option<f64> {
+        match self.0.get(name) {
+            Some(MapProperty::Float(value)) => Some(*value),
+            Some(MapProperty::Int(value)) => Some(*value as f64),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum MapShape {
+    Point,
+    Rect(Vec2),
+    Ellipse(Vec2),
+    /// Points relative to the object position.
+    Polygon(Vec<Vec2>),
+}
+
+/// Object from an object layer, spawned as a child of the map entity.
+#[derive(Component, Debug, Clone)]
+pub struct MapObject {
+    pub name: String,
+    /// Class of the object in the editor, decides which component it gets.
+    pub kind: String,
+    /// Center of rects and ellipses, origin of points and polygons, relative to the
+    /// top left corner of the map with y pointing up.
+    pub position: Vec2,
+    pub shape: MapShape,
+    pub properties: MapProperties,
+}
+
+/// Place where an entering player or a spawned entity appears, `spawn_point` objects.
+#[derive(Component, Debug, Clone)]
+pub struct SpawnPoint {
+    pub name: String,
+}
+
+/// Leads to a spawn point in another location, `door` objects with `location`
+/// and `spawn` properties.
+#[derive(Component, Debug, Clone)]
+pub struct Door {
+    pub location: String,
+    pub spawn: String,
+}
+
+/// Solid geometry, `collider` objects.
+#[derive(Component, Debug, Clone)]
+pub struct MapCollider;
+
+/// Where an NPC should appear, `npc_spawner` objects with an `npc` property. Nothing spawns
+/// from them while the NPC plugin is disabled.
+#[derive(Component, Debug, Clone)]
+pub struct NpcSpawner {
+    pub npc: String,
+}
+
+/// Item lying on the map for the player to pick up, `collectible` objects with an `item`
+/// and an optional `count` property.
+#[derive(Component, Debug, Clone)]
+pub struct MapCollectible {
+    pub item: String,
+    pub count: u32,
+}
+
+#[derive(Component, Debug, Clone)]
+pub struct TiledMapHandle(pub Handle<TiledMap>);
+
+/// Added to the map entity once its layers and objects are spawned.
+#[derive(Component, Debug)]
+pub struct TiledMapSpawned;
+
+#[derive(Bundle)]
+pub struct TiledMapBundle {
+    pub map: TiledMapHandle,
+    pub spatial: SpatialBundle,
+}
+
+impl TiledMapBundle {
+    /// `transform` places the top left corner of the map.
+    pub fn new(map: Handle<TiledMap>, transform: Transform) -> Self {
+        Self {
+            map: TiledMapHandle(map),
+            spatial: SpatialBundle::from_transform(transform),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum TiledMapError {
+    Io(io::Error),
+    Json(serde_json::Error),
+    Unsupported(String),
+}
+
+impl fmt::Display for TiledMapError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TiledMapError::Io(error) => write!(f, "failed to read the map: {}", error),
+            TiledMapError::Json(error) => write!(f, "failed to parse the map: {}", error),
+            TiledMapError::Unsupported(feature) => write!(f, "unsupported map: {}", feature),
+        }
+    }
+}
+
+impl std::error::Error for TiledMapError {}
+
+impl From<io::Error> for TiledMapError {
+    fn from(error: io::Error) -> Self {
+        TiledMapError::Io(error)
+    }
+}
+
+impl From<serde_json::Error> for TiledMapError {
+    fn from(error: serde_json::Error) -> Self {
+        TiledMapError::Json(error)
+    }
+}
+
+#[derive(Deserialize)]
+struct RawMap {
+    width: u32,
+    height: u32,
+    tilewidth: u32,
+    tileheight: u32,
+    #[serde(default)]
+    infinite: bool,
+    #[serde(default)]
+    layers: Vec<RawLayer>,
+    #[serde(default)]
+    tilesets: Vec<Value>,
+    #[serde(default)]
+    properties: Vec<RawProperty>,
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+enum RawLayer {
+    Tilelayer {
+        name: String,
+        width: u32,
+        height: u32,
+        #[serde(default)]
+        data: Vec<u32>,
+        #[serde(default)]
+        encoding: Option<String>,
+        #[serde(default = "default_visible")]
+        visible: bool,
+    },
+    Objectgroup {
+        #[serde(default)]
+        objects: Vec<RawObject>,
+        #[serde(default = "default_visible")]
+        visible: bool,
+    },
+    Imagelayer {
+        name: String,
+        image: String,
+        #[serde(default)]
+        offsetx: f32,
+        #[serde(default)]
+        offsety: f32,
+        #[serde(default = "default_visible")]
+        visible: bool,
+    },
+    Group {
+        #[serde(default)]
+        layers: Vec<RawLayer>,
+        #[serde(default = "default_visible")]
+        visible: bool,
+    },
+}
+
+impl RawLayer {
+    fn visible(&self) -> bool {
+        match self {
+            RawLayer::Tilelayer { visible, .. }
+            | RawLayer::Objectgroup { visible, .. }
+            | RawLayer::Imagelayer { visible, .. }
+            | RawLayer::Group { visible, .. } => *visible,
+        }
+    }
+}
+
+fn default_visible() -> bool {
+    true
+}
+
+/// Layers to build the map from with their depth. Groups are flattened, every layer
+/// keeps its place in the drawing order. Hidden layers, hidden groups with everything
+/// inside them and hidden objects are left out.
+fn visible_layers(layers: Vec<RawLayer>) -> Vec<(f32, RawLayer)> {
+    let mut visible_layers = Vec::new();
+    let mut pending: Vec<RawLayer> = layers.into_iter().rev().collect();
+    let mut order = 0;
+    while let Some(layer) = pending.pop() {
+        let z = order as f32 * LAYER_Z_STEP;
+        order += 1;
+
+        match layer {
+            layer if !layer.visible() => {}
+            RawLayer::Group { layers, .. } => {
+                pending.extend(layers.into_iter().rev());
+            }
+            RawLayer::Objectgroup {
+                mut objects,
+                visible,
+            } => {
+                objects.retain(|object| object.visible);
+                visible_layers.push((z, RawLayer::Objectgroup { objects, visible }));
+            }
+            layer => visible_layers.push((z, layer)),
+        }
+    }
+
+    visible_layers
+}
+
+#[derive(Deserialize)]
+struct RawTileset {
+    image: String,
+    imagewidth: u32,
+    imageheight: u32,
+    tilewidth: u32,
+    tileheight: u32,
+    #[serde(default)]
+    margin: u32,
+    #[serde(default)]
+    spacing: u32,
+}
+
+#[derive(Deserialize)]
+struct RawObject {
+    #[serde(default)]
+    name: String,
+    /// Called `class` since Tiled 1.9.
+    #[serde(default, rename = "type", alias = "class")]
+    kind: String,
+    x: f32,
+    y: f32,
+    #[serde(default)]
+    width: f32,
+    #[serde(default)]
+    height: f32,
+    /// Degrees clockwise around the top left corner, not supported.
+    #[serde(default)]
+    rotation: f32,
+    #[serde(default = "default_visible")]
+    visible: bool,
+    #[serde(default)]
+    point: bool,
+    #[serde(default)]
+    ellipse: bool,
+    #[serde(default)]
+    polygon: Option<Vec<RawPoint>>,
+    #[serde(default)]
+    properties: Vec<RawProperty>,
+}
+
+#[derive(Deserialize)]
+struct RawPoint {
+    x: f32,
+    y: f32,
+}
+
+#[derive(Deserialize)]
+struct RawProperty {
+    name: String,
+    value: Value,
+}
+
+fn parse_properties(raw_properties: Vec<RawProperty>) -> MapProperties {
+    let properties = raw_properties
+        .into_iter()
+        .filter_map(|property| {
+            let value = match property.value {
+                Value::Bool(value) => MapProperty::Bool(value),
+                Value::Number(number) => match number.as_i64() {
+                    Some(value) => MapProperty::Int(value),
+                    None => MapProperty::Float(number.as_f64()?),
+                },
+                Value::String(value) => MapProperty::String(value),
+                _ => return None,
+            };
+            Some((property.name, value))
+        })
+        .collect();
+
+    MapProperties(properties)
+}
+
+fn parse_object(raw: RawObject) -> MapObject {
+    if raw.rotation != 0.0 {
+        warn!(
+            "Map object {:?} is rotated by {} degrees, it is placed unrotated",
+            raw.name, raw.rotation
+        );
+    }
+
+    let origin = Vec2::new(raw.x, -raw.y);
+    let size = Vec2::new(raw.width, raw.height);
+
+    let (position, shape) = if let Some(polygon) = raw.polygon {
+        let points = polygon
+            .into_iter()
+            .map(|point| Vec2::new(point.x, -point.y))
+            .collect();
+        (origin, MapShape::Polygon(points))
+    } else if raw.point {
+        (origin, MapShape::Point)
+    } else {
+        let center = origin + Vec2::new(size.x, -size.y) / 2.0;
+        if raw.ellipse {
+            (center, MapShape::Ellipse(size))
+        } else {
+            (center, MapShape::Rect(size))
+        }
+    };
+
+    MapObject {
+        name: raw.name,
+        kind: raw.kind,
+        position,
+        shape,
+        properties: parse_properties(raw.properties),
+    }
+}
+
+/// Tilesets of the map in `firstgid` order, so a gid can be resolved to its tileset.
+struct LoadedTilesets {
+    first_gids: Vec<u32>,
+}
+
+impl LoadedTilesets {
+    fn tile(&self, gid: u32) -> Option<Tile> {
+        let flip_x = gid & FLIPPED_HORIZONTALLY != 0;
+        let flip_y = gid & FLIPPED_VERTICALLY != 0;
+        let gid = gid & !FLIP_FLAGS;
+        if gid == 0 {
+            return None;
+        }
+
+        let tileset = self
+            .first_gids
+            .iter()
+            .rposition(|first_gid| *first_gid <= gid)?;
+        Some(Tile::new(tileset, gid - self.first_gids[tileset]).with_flip(flip_x, flip_y))
+    }
+}
+
+#[derive(Default)]
+pub struct TiledMapLoader;
+
+impl TiledMapLoader {
+    async fn load_tileset<'a>(
+        load_context: &mut LoadContext<'a>,
+        value: Value,
+    ) -> Result<(u32, Tileset), TiledMapError> {
+        let first_gid = value
+            .get("firstgid")
+            .and_then(Value::as_u64)
+            .ok_or_else(|| TiledMapError::Unsupported("tileset without firstgid".to_string()))?
+            as u32;
+
+        // External tilesets resolve their image relative to their own file.
+        let (raw, base_path) = match value.get("source").and_then(Value::as_str) {
+            Some(source) => {
+                let path = resolve(load_context, source)?;
+                let bytes = load_context
+                    .read_asset_bytes(path.clone())
+                    .await
+                    .map_err(|error| io::Error::new(io::ErrorKind::NotFound, error))?;
+                (serde_json::from_slice::<RawTileset>(&bytes)?, path)
+            }
+            None => (
+                serde_json::from_value::<RawTileset>(value)?,
+                load_context.asset_path().to_string(),
+            ),
+        };
+
+        let image_path = resolve_from(&base_path, &raw.image)?;
+        let tileset = Tileset::new(
+            load_context.load(image_path),
+            UVec2::new(raw.imagewidth, raw.imageheight),
+            UVec2::new(raw.tilewidth, raw.tileheight),
+        )
+        .with_spacing(raw.margin, raw.spacing);
+
+        Ok((first_gid, tileset))
+    }
+}
+
+fn resolve(load_context: &LoadContext, path: &str) -> Result<String, TiledMapError> {
+    resolve_from(&load_context.asset_path().to_string(), path)
+}
+
+/// Path of `path` written relative to the file at `base`.
+fn resolve_from(base: &str, path: &str) -> Result<String, TiledMapError> {
+    bevy::asset::AssetPath::parse(base)
+        .resolve_embed(path)
+        .map(|path| path.to_string())
+        .map_err(|error| TiledMapError::Unsupported(format!("path {}: {}", path, error)))
+}
+
+impl AssetLoader for TiledMapLoader {
+    type Asset = TiledMap;
+    type Settings = ();
+    type Error = TiledMapError;
+
+    async fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader<'_>,
+        _settings: &'a (),
+        load_context: &'a mut LoadContext<'_>,
+    ) -> Result<TiledMap, TiledMapError> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        let raw: RawMap = serde_json::from_slice(&bytes)?;
+
+        if raw.infinite {
+            return Err(TiledMapError::Unsupported("infinite maps".to_string()));
+        }
+
+        let mut tile_map = TileMap::new(Vec2::new(raw.tilewidth as f32, raw.tileheight as f32));
+        let mut first_gids = Vec::new();
+        for value in raw.tilesets {
+            let (first_gid, tileset) = Self::load_tileset(load_context, value).await?;
+            first_gids.push(first_gid);
+            tile_map.add_tileset(tileset);
+        }
+        let tilesets = LoadedTilesets { first_gids };
+
+        let mut image_layers = Vec::new();
+        let mut objects = Vec::new();
+
+        for (z, layer) in visible_layers(raw.layers) {
+            match layer {
+                RawLayer::Tilelayer {
+                    name,
+                    width,
+                    height,
+                    data,
+                    encoding,
+                    ..
+                } => {
+                    if encoding.is_some_and(|encoding| encoding != "csv") {
+                        return Err(TiledMapError::Unsupported(format!(
+                            "layer {} is not CSV encoded",
+                            name
+                        )));
+                    }
+
+                    let mut tile_layer = TileLayer::new(&name, UVec2::new(width, height));
+                    for (index, gid) in data.into_iter().enumerate() {
+                        let index = index as u32;
+                        let position = UVec2::new(index % width, index / width);
+                        tile_layer.set(position, tilesets.tile(gid));
+                    }
+                    tile_layer.z = z;
+                    tile_map.layers.push(tile_layer);
+                }
+                RawLayer::Objectgroup {
+                    objects: raw_objects,
+                    ..
+                } => {
+                    objects.extend(raw_objects.into_iter().map(parse_object));
+                }
+                RawLayer::Imagelayer {
+                    name,
+                    image,
+                    offsetx,
+                    offsety,
+                    ..
+                } => {
+                    let image_path = resolve(load_context, &image)?;
+                    image_layers.push(MapImageLayer {
+                        name,
+                        image: load_context.load(image_path),
+                        offset: Vec2::new(offsetx, -offsety),
+                        z,
+                    });
+                }
+                // Already flattened into their layers.
+                RawLayer::Group { .. } => {}
+            }
+        }
+
+        Ok(TiledMap {
+            size: Vec2::new(
+                (raw.width * raw.tilewidth) as f32,
+                (raw.height * raw.tileheight) as f32,
+            ),
+            tile_map,
+            image_layers,
+            objects,
+            properties: parse_properties(raw.properties),
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["tmj"]
+    }
+}
+
+/// Turns loaded maps into a [`TileMap`] with image layers and objects as children.
+pub fn spawn_tiled_maps(
+    mut commands: Commands,
+    tiled_maps: Res<Assets<TiledMap>>,
+    map_query: Query<(Entity, &TiledMapHandle), Without<TiledMapSpawned>>,
+) {
+    for (entity, map_handle) in map_query.iter() {
+        let Some(map) = tiled_maps.get(&map_handle.0) else {
+            continue;
+        };
+
+        commands
+            .entity(entity)
+            .insert((
+                map.tile_map.clone(),
+                map.properties.clone(),
+                TiledMapSpawned,
+            ))
+            .with_children(|parent| {
+                for image_layer in map.image_layers.iter() {
+                    parent.spawn(SpriteBundle {
+                        texture: image_layer.image.clone(),
+                        sprite: Sprite {
+                            anchor: Anchor::TopLeft,
+                            ..default()
+                        },
+                        transform: Transform::from_translation(
+                            image_layer.offset.extend(image_layer.z),
+                        ),
+                        ..default()
+                    });
+                }
+
+                for object in map.objects.iter() {
+                    let mut object_entity = parent.spawn((
+                        SpatialBundle::from_transform(Transform::from_translation(
+                            object.position.extend(0.0),
+                        )),
+                        object.clone(),
+                        object.properties.clone(),
+                    ));
+
+                    match object.kind.as_str() {
+                        "spawn_point" => {
+                            object_entity.insert(SpawnPoint {
+                                name: object.name.clone(),
+                            });
+                        }
+                        "door" => {
+                            object_entity.insert(Door {
+                                location: object
+                                    .properties
+                                    .get_str("location")
+                                    .unwrap_or_default()
+                                    .to_string(),
+                                spawn: object
+                                    .properties
+                                    .get_str("spawn")
+                                    .unwrap_or_default()
+                                    .to_string(),
+                            });
+                        }
+                        "collider" => {
+                            object_entity.insert(MapCollider);
+                        }
+                        "npc_spawner" => {
+                            object_entity.insert(NpcSpawner {
+                                npc: object
+                                    .properties
+                                    .get_str("npc")
+                                    .unwrap_or_default()
+                                    .to_string(),
+                            });
+                        }
+                        "collectible" => {
+                            object_entity.insert(MapCollectible {
+                                item: object
+                                    .properties
+                                    .get_str("item")
+                                    .unwrap_or_default()
+                                    .to_string(),
+                                count: object.properties.get_int("count").unwrap_or(1).max(1)
+                                    as u32,
+                            });
+                        }
+                        // Read through `TiledMap::bounds`.
+                        "" | "bounds" => {}
+                        kind => warn!("Unknown map object kind {}", kind),
+                    }
+                }
+            });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn object(json: &str) -> MapObject {
+        parse_object(serde_json::from_str(json).unwrap())
+    }
+
+    fn properties(json: &str) -> MapProperties {
+        parse_properties(serde_json::from_str(json).unwrap())
+    }
+
+    #[test]
+    fn rect_is_centered_with_y_up() {
+        let door = object(
+            r#"{"name": "exit", "type": "door", "x": 32, "y": 64, "width": 16, "height": 8}"#,
+        );
+
+        assert_eq!(door.kind, "door");
+        assert_eq!(door.position, Vec2::new(40.0, -68.0));
+        assert_eq!(door.shape, MapShape::Rect(Vec2::new(16.0, 8.0)));
+    }
+
+    #[test]
+    fn class_is_read_as_kind() {
+        let spawn = object(r#"{"class": "spawn_point", "x": 0, "y": 0, "point": true}"#);
+
+        assert_eq!(spawn.kind, "spawn_point");
+    }
+
+    #[test]
+    fn point_stays_at_its_origin() {
+        let spawn = object(r#"{"name": "start", "x": 64, "y": 512, "point": true}"#);
+
+        assert_eq!(spawn.position, Vec2::new(64.0, -512.0));
+        assert_eq!(spawn.shape, MapShape::Point);
+    }
+
+    #[test]
+    fn ellipse_is_centered() {
+        let rock = object(r#"{"x": 10, "y": 20, "width": 30, "height": 40, "ellipse": true}"#);
+
+        assert_eq!(rock.position, Vec2::new(25.0, -40.0));
+        assert_eq!(rock.shape, MapShape::Ellipse(Vec2::new(30.0, 40.0)));
+    }
+
+    #[test]
+    fn polygon_points_are_flipped_around_the_origin() {
+        let wall = object(
+            r#"{"x": 100, "y": 50, "polygon": [{"x": 0, "y": 0}, {"x": 10, "y": 0}, {"x": 0, "y": 20}]}"#,
+        );
+
+        assert_eq!(wall.position, Vec2::new(100.0, -50.0));
+        assert_eq!(
+            wall.shape,
+            MapShape::Polygon(vec![
+                Vec2::new(0.0, 0.0),
+                Vec2::new(10.0, 0.0),
+                Vec2::new(0.0, -20.0),
+            ])
+        );
+    }
+
+    #[test]
+    fn properties_keep_their_type() {
+        let properties = properties(
+            r##"[
+                {"name": "location", "type": "string", "value": "station"},
+                {"name": "count", "type": "int", "value": 3},
+                {"name": "speed", "type": "float", "value": 1.5},
+                {"name": "locked", "type": "bool", "value": true},
+                {"name": "tint", "type": "color", "value": "#ff0000"},
+                {"name": "target", "type": "object", "value": null}
+            ]"##,
+        );
+
+        assert_eq!(properties.get_str("location"), Some("station"));
+        assert_eq!(properties.get_int("count"), Some(3));
+        assert_eq!(properties.get_float("count"), Some(3.0));
+        assert_eq!(properties.get_float("speed"), Some(1.5));
+        assert_eq!(properties.get_bool("locked"), Some(true));
+        // Colors come as strings, unknown value types are dropped.
+        assert_eq!(properties.get_str("tint"), Some("#ff0000"));
+        assert!(!properties.0.contains_key("target"));
+        assert_eq!(properties.get_int("location"), None);
+    }
+
+    #[test]
+    fn gid_resolves_to_its_tileset() {
+        let tilesets = LoadedTilesets {
+            first_gids: vec![1, 442],
+        };
+
+        assert_eq!(tilesets.tile(0), None);
+        assert_eq!(tilesets.tile(1), Some(Tile::new(0, 0)));
+        assert_eq!(tilesets.tile(441), Some(Tile::new(0, 440)));
+        assert_eq!(tilesets.tile(442), Some(Tile::new(1, 0)));
+        assert_eq!(tilesets.tile(450), Some(Tile::new(1, 8)));
+    }
+
+    #[test]
+    fn flip_flags_are_stripped_from_the_gid() {
+        let tilesets = LoadedTilesets {
+            first_gids: vec![1],
+        };
+
+        assert_eq!(
+            tilesets.tile(5 | FLIPPED_HORIZONTALLY),
+            Some(Tile::new(0, 4).with_flip(true, false))
+        );
+        assert_eq!(
+            tilesets.tile(5 | FLIPPED_VERTICALLY),
+            Some(Tile::new(0, 4).with_flip(false, true))
+        );
+        // The diagonal flag is not supported and only dropped.
+        assert_eq!(tilesets.tile(5 | 0x2000_0000), Some(Tile::new(0, 4)));
+        assert_eq!(tilesets.tile(FLIPPED_HORIZONTALLY), None);
+    }
+
+    fn layers(json: &str) -> Vec<(f32, RawLayer)> {
+        visible_layers(serde_json::from_str(json).unwrap())
+    }
+
+    fn object_names(layers: &[(f32, RawLayer)]) -> Vec<&str> {
+        layers
+            .iter()
+            .filter_map(|(_, layer)| match layer {
+                RawLayer::Objectgroup { objects, .. } => Some(objects),
+                _ => None,
+            })
+            .flatten()
+            .map(|object| object.name.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn hidden_object_layer_is_left_out() {
+        let layers = layers(
+            r#"[
+                {"type": "objectgroup", "visible": false, "objects": [
+                    {"name": "coin", "x": 0, "y": 0}
+                ]}
+            ]"#,
+        );
+
+        assert!(layers.is_empty());
+    }
+
+    #[test]
+    fn hidden_object_is_left_out() {
+        let layers = layers(
+            r#"[
+                {"type": "objectgroup", "objects": [
+                    {"name": "coin", "x": 0, "y": 0, "visible": false},
+                    {"name": "door", "x": 0, "y": 0}
+                ]}
+            ]"#,
+        );
+
+        assert_eq!(object_names(&layers), vec!["door"]);
+    }
+
+    #[test]
+    fn hidden_group_is_left_out_with_its_layers() {
+        let layers = layers(
+            r#"[
+                {"type": "group", "visible": false, "layers": [
+                    {"type": "tilelayer", "name": "ground", "width": 1, "height": 1, "data": [1]},
+                    {"type": "objectgroup", "objects": [{"name": "coin", "x": 0, "y": 0}]},
+                    {"type": "group", "layers": [
+                        {"type": "objectgroup", "objects": [{"name": "door", "x": 0, "y": 0}]}
+                    ]}
+                ]}
+            ]"#,
+        );
+
+        assert!(layers.is_empty());
+    }
+
+    #[test]
+    fn groups_are_flattened_in_drawing_order() {
+        let layers = layers(
+            r#"[
+                {"type": "objectgroup", "objects": [{"name": "first", "x": 0, "y": 0}]},
+                {"type": "group", "layers": [
+                    {"type": "objectgroup", "objects": [{"name": "second", "x": 0, "y": 0}]}
+                ]},
+                {"type": "objectgroup", "objects": [{"name": "third", "x": 0, "y": 0}]}
+            ]"#,
+        );
+
+        assert_eq!(object_names(&layers), vec!["first", "second", "third"]);
+        assert!(layers.windows(2).all(|pair| pair[0].0 < pair[1].0));
+    }
+}
@@ -0,0 +1,355 @@
+use std::collections::HashMap;
+
+use bevy::prelude::{
+    default, App, Assets, BuildChildren, Bundle, Changed, Children, ColorMaterial, Commands,
+    Component, DespawnRecursiveExt, Entity, Handle, Image, Mesh, Plugin, Query, Rect, ResMut,
+    SpatialBundle, Transform, UVec2, Update, Vec2, With,
+};
+use bevy::render::mesh::{Indices, PrimitiveTopology};
+use bevy::render::render_asset::RenderAssetUsages;
+use bevy::sprite::{MaterialMesh2dBundle, Mesh2dHandle};
+
+use crate::tilemap::tiled::TiledMapPlugin;
+
+/// Width and height of a chunk in tiles, each chunk of a layer is drawn with one mesh
+/// per tileset it uses.
+pub const CHUNK_SIZE: u32 = 16;
+/// Distance between two layers of the same map, later layers are drawn on top.
+pub const LAYER_Z_STEP: f32 = 0.01;
+/// Pulls the UVs inside the tile by a fraction of a texel so neighbouring tiles
+/// of the tileset do not bleed into the edges.
+const UV_INSET: f32 = 0.1;
+
+pub struct TileMapPlugin;
+
+impl Plugin for TileMapPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(TiledMapPlugin)
+            .add_systems(Update, build_tilemap_chunks);
+    }
+}
+
+/// Image cut into a grid of equally sized tiles, numbered row by row from the top left.
+#[derive(Debug, Clone)]
+pub struct Tileset {
+    pub texture: Handle<Image>,
+    pub image_size: UVec2,
+    pub tile_size: UVec2,
+    /// Empty pixels around the whole grid.
+    pub margin: u32,
+    /// Empty pixels between two tiles.
+    pub spacing: u32,
+}
+
+impl Tileset {
+    pub fn new(texture: Handle<Image>, image_size: UVec2, tile_size: UVec2) -> Self {
+        Self {
+            texture,
+            image_size,
+            tile_size,
+            margin: 0,
+            spacing: 0,
+        }
+    }
+
+    pub fn with_spacing(mut self, margin: u32, spacing: u32) -> Self {
+        self.margin = margin;
+        self.spacing = spacing;
+        self
+    }
+
+    /// Number of whole tiles in a row, leftover pixels on the right are ignored.
+    pub fn columns(&self) -> u32 {
+        let usable_width = self.image_size.x.saturating_sub(self.margin * 2) + self.spacing;
+        (usable_width / (self.tile_size.x + self.spacing)).max(1)
+    }
+
+    /// Pixel rect of the tile inside the tileset image.
+    pub fn tile_rect(&self, index: u32) -> Rect {
+        let columns = self.columns();
+        let cell = UVec2::new(index % columns, index / columns);
+        let min = UVec2::splat(self.margin) + cell * (self.tile_size + self.spacing);
+
+        Rect::from_corners(min.as_vec2(), (min + self.tile_size).as_vec2())
+    }
+
+    fn tile_uvs(&self, index: u32) -> Rect {
+        let rect = self.tile_rect(index).inflate(-UV_INSET);
+        let image_size = self.image_size.as_vec2();
+
+        Rect::from_corners(rect.min / image_size, rect.max / image_size)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Tile {
+    /// Index into [`TileMap::tilesets`].
+    pub tileset: usize,
+    /// Index of the tile inside its tileset.
+    pub index: u32,
+    pub flip_x: bool,
+    pub flip_y: bool,
+}
+
+impl Tile {
+    pub fn new(tileset: usize, index: u32) -> Self {
+        Self {
+            tileset,
+            index,
+            flip_x: false,
+            flip_y: false,
+        }
+    }
+
+    pub fn with_flip(mut self, flip_x: bool, flip_y: bool) -> Self {
+        self.flip_x = flip_x;
+        self.flip_y = flip_y;
+        self
+    }
+}
+
+/// Grid of tiles, the first row is the top one.
+#[derive(Debug, Clone)]
+pub struct TileLayer {
+    pub name: String,
+    pub size: UVec2,
+    pub tiles: Vec<Option<Tile>>,
+    /// Depth relative to the map entity.
+    pub z: f32,
+}
+
+impl TileLayer {
+    pub fn new(name: &str, size: UVec2) -> Self {
+        Self {
+            name: name.to_string(),
+            size,
+            tiles: vec![None; (size.x * size.y) as usize],
+            z: 0.0,
+        }
+    }
+
+    fn position_index(&self, position: UVec2) -> Option<usize> {
+        (position.x < self.size.x && position.y < self.size.y)
+            .then(|| (position.y * self.size.x + position.x) as usize)
+    }
+
+    pub fn get(&self, position: UVec2) -> Option<Tile> {
+        self.position_index(position)
+            .and_then(|index| self.tiles[index])
+    }
+
+    pub fn set(&mut self, position: UVec2, tile: Option<Tile>) {
+        if let Some(index) = self.position_index(position) {
+            self.tiles[index] = tile;
+        }
+    }
+}
+
+/// Layered tile grids drawn from one or more tilesets. The entity's transform places
+/// the top left corner of the map, the chunk meshes are spawned as its children.
+#[derive(Component, Debug, Clone)]
+pub struct TileMap {
+    /// Size of one tile in the world.
+    pub tile_size: Vec2,
+    pub tilesets: Vec<Tileset>,
+    pub layers: Vec<TileLayer>,
+}
+
+impl TileMap {
+    pub fn new(tile_size: Vec2) -> Self {
+        Self {
+            tile_size,
+            tilesets: Vec::new(),
+            layers: Vec::new(),
+        }
+    }
+
+    /// Returns the index that [`Tile::tileset`] refers to.
+    pub fn add_tileset(&mut self, tileset: Tileset) -> usize {
+        self.tilesets.push(tileset);
+        self.tilesets.len() - 1
+    }
+
+    /// Puts the layer on top of the ones added before it.
+    pub fn add_layer(&mut self, mut layer: TileLayer) {
+        layer.z = self.layers.len() as f32 * LAYER_Z_STEP;
+        self.layers.push(layer);
+    }
+
+    /// Size in tiles, enough to hold the largest layer.
+    pub fn size(&self) -> UVec2 {
+        self.layers
+            .iter()
+            .fold(UVec2::ZERO, |size, layer| size.max(layer.size))
+    }
+
+    pub fn world_size(&self) -> Vec2 {
+        self.size().as_vec2() * self.tile_size
+    }
+
+    /// Center of the tile relative to the top left corner of the map.
+    pub fn tile_center(&self, position: UVec2) -> Vec2 {
+        Vec2::new(
+            (position.x as f32 + 0.5) * self.tile_size.x,
+            -(position.y as f32 + 0.5) * self.tile_size.y,
+        )
+    }
+}
+
+#[derive(Bundle)]
+pub struct TileMapBundle {
+    pub tile_map: TileMap,
+    pub spatial: SpatialBundle,
+}
+
+impl TileMapBundle {
+    pub fn new(tile_map: TileMap, transform: Transform) -> Self {
+        Self {
+            tile_map,
+            spatial: SpatialBundle::from_transform(transform),
+        }
+    }
+}
+
+#[derive(Component, Debug)]
+pub struct TileChunk {
+    pub layer: usize,
+    pub chunk: UVec2,
+}
+
+/// Quads of a single tileset inside one chunk.
+#[derive(Default)]
+struct ChunkMeshData {
+    positions: Vec<[f32; 3]>,
+    uvs: Vec<[f32; 2]>,
+    indices: Vec<u32>,
+}
+
+impl ChunkMeshData {
+    fn push_quad(&mut self, top_left: Vec2, size: Vec2, tile: Tile, mut uvs: Rect) {
+        let first = self.positions.len() as u32;
+        let bottom_right = top_left + Vec2::new(size.x, -size.y);
+        if tile.flip_x {
+            std::mem::swap(&mut uvs.min.x, &mut uvs.max.x);
+        }
+        if tile.flip_y {
+            std::mem::swap(&mut uvs.min.y, &mut uvs.max.y);
+        }
+
+        self.positions.extend([
+            [top_left.x, top_left.y, 0.0],
+            [bottom_right.x, top_left.y, 0.0],
+            [bottom_right.x, bottom_right.y, 0.0],
+            [top_left.x, bottom_right.y, 0.0],
+        ]);
+        self.uvs.extend([
+            [uvs.min.x, uvs.min.y],
+            [uvs.max.x, uvs.min.y],
+            [uvs.max.x, uvs.max.y],
+            [uvs.min.x, uvs.max.y],
+        ]);
+        self.indices
+            .extend([first, first + 3, first + 2, first, first + 2, first + 1]);
+    }
+
+    fn into_mesh(self) -> Mesh {
+        Mesh::new(
+            PrimitiveTopology::TriangleList,
+            RenderAssetUsages::RENDER_WORLD,
+        )
+        .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, self.positions)
+        .with_inserted_attribute(Mesh::ATTRIBUTE_UV_0, self.uvs)
+        .with_inserted_indices(Indices::U32(self.indices))
+    }
+}
+
+fn build_chunk_meshes(tile_map: &TileMap, layer: &TileLayer, chunk: UVec2) -> Vec<(usize, Mesh)> {
+    let mut meshes: HashMap<usize, ChunkMeshData> = HashMap::new();
+    let from = chunk * CHUNK_SIZE;
+    let to = (from + UVec2::splat(CHUNK_SIZE)).min(layer.size);
+
+    for y in from.y..to.y {
+        for x in from.x..to.x {
+            let Some(tile) = layer.get(UVec2::new(x, y)) else {
+                continue;
+            };
+            let Some(tileset) = tile_map.tilesets.get(tile.tileset) else {
+                continue;
+            };
+
+            let local = UVec2::new(x, y) - from;
+            let top_left = Vec2::new(
+                local.x as f32 * tile_map.tile_size.x,
+                -(local.y as f32) * tile_map.tile_size.y,
+            );
+
+            meshes.entry(tile.tileset).or_default().push_quad(
+                top_left,
+                tile_map.tile_size,
+                tile,
+                tileset.tile_uvs(tile.index),
+            );
+        }
+    }
+
+    meshes
+        .into_iter()
+        .map(|(tileset, data)| (tileset, data.into_mesh()))
+        .collect()
+}
+
+/// Rebuilds the chunk meshes of every map that was added or changed.
+pub fn build_tilemap_chunks(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    tile_map_query: Query<(Entity, &TileMap, Option<&Children>), Changed<TileMap>>,
+    chunk_query: Query<(), With<TileChunk>>,
+) {
+    for (entity, tile_map, children) in tile_map_query.iter() {
+        for child in children.into_iter().flatten() {
+            if chunk_query.contains(*child) {
+                commands.entity(*child).despawn_recursive();
+            }
+        }
+
+        let tileset_materials: Vec<Handle<ColorMaterial>> = tile_map
+            .tilesets
+            .iter()
+            .map(|tileset| materials.add(tileset.texture.clone()))
+            .collect();
+        let chunk_size = tile_map.tile_size * CHUNK_SIZE as f32;
+
+        commands.entity(entity).with_children(|parent| {
+            for (layer_index, layer) in tile_map.layers.iter().enumerate() {
+                let chunks = (layer.size + UVec2::splat(CHUNK_SIZE - 1)) / CHUNK_SIZE;
+
+                for chunk_y in 0..chunks.y {
+                    for chunk_x in 0..chunks.x {
+                        let chunk = UVec2::new(chunk_x, chunk_y);
+                        let translation = Vec2::new(
+                            chunk.x as f32 * chunk_size.x,
+                            -(chunk.y as f32) * chunk_size.y,
+                        )
+                        .extend(layer.z);
+
+                        for (tileset, mesh) in build_chunk_meshes(tile_map, layer, chunk) {
+                            parent.spawn((
+                                MaterialMesh2dBundle {
+                                    mesh: Mesh2dHandle(meshes.add(mesh)),
+                                    material: tileset_materials[tileset].clone(),
+                                    transform: Transform::from_translation(translation),
+                                    ..default()
+                                },
+                                TileChunk {
+                                    layer: layer_index,
+                                    chunk,
+                                },
+                            ));
+                        }
+                    }
+                }
+            }
+        });
+    }
+}
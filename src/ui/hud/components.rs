@@ -0,0 +1,18 @@
+use bevy::prelude::Component;
+
+#[derive(Component)]
+pub struct Hud;
+
+/// Filled part of a status bar, its width follows the stat.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HudBar {
+    Health,
+    Stamina,
+}
+
+/// Coin count of the player.
+#[derive(Component)]
+pub struct InGameScore;
+
+#[derive(Component)]
+pub struct ObjectiveText;
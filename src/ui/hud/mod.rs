@@ -0,0 +1,31 @@
+use bevy::prelude::{in_state, resource_changed, App, IntoSystemConfigs, OnEnter, Plugin, Update};
+
+use crate::game::objective::objective::Objective;
+use crate::AppState;
+use systems::layout::spawn_hud;
+use systems::updates::{
+    update_coin_counter, update_health_bar, update_objective_text, update_stamina_bar,
+};
+
+pub mod components;
+mod styles;
+mod systems;
+
+/// Player status drawn over the game: health and stamina bars, coins and the objective.
+pub struct HudPlugin;
+
+impl Plugin for HudPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(AppState::Game), spawn_hud)
+            .add_systems(
+                Update,
+                (
+                    update_health_bar,
+                    update_stamina_bar,
+                    update_coin_counter,
+                    update_objective_text.run_if(resource_changed::<Objective>),
+                )
+                    .run_if(in_state(AppState::Game)),
+            );
+    }
+}
@@ -0,0 +1,44 @@
+use bevy::prelude::{
+    AlignItems, Color, FlexDirection, JustifyContent, PositionType, Style, UiRect, Val,
+};
+
+pub const HEALTH_BAR_COLOR: Color = Color::srgb(0.8, 0.15, 0.15);
+pub const STAMINA_BAR_COLOR: Color = Color::srgb(0.25, 0.7, 0.3);
+pub const BAR_BACKGROUND_COLOR: Color = Color::srgba(0.0, 0.0, 0.0, 0.6);
+
+pub fn get_hud_style() -> Style {
+    Style {
+        position_type: PositionType::Absolute,
+        flex_direction: FlexDirection::Row,
+        justify_content: JustifyContent::SpaceBetween,
+        align_items: AlignItems::FlexStart,
+        width: Val::Percent(100.0),
+        padding: UiRect::all(Val::Px(24.0)),
+        ..Style::DEFAULT
+    }
+}
+
+pub fn get_hud_column_style() -> Style {
+    Style {
+        flex_direction: FlexDirection::Column,
+        row_gap: Val::Px(8.0),
+        ..Style::DEFAULT
+    }
+}
+
+pub fn get_bar_style() -> Style {
+    Style {
+        width: Val::Px(320.0),
+        height: Val::Px(20.0),
+        padding: UiRect::all(Val::Px(3.0)),
+        ..Style::DEFAULT
+    }
+}
+
+pub fn get_bar_fill_style() -> Style {
+    Style {
+        width: Val::Percent(100.0),
+        height: Val::Percent(100.0),
+        ..Style::DEFAULT
+    }
+}
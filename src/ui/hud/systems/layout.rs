@@ -0,0 +1,70 @@
+use bevy::prelude::{
+    default, BuildChildren, ChildBuilder, Color, Commands, Entity, NodeBundle, Res, StateScoped,
+    TextBundle,
+};
+
+use crate::ui::hud::components::{Hud, HudBar, InGameScore, ObjectiveText};
+use crate::ui::hud::styles::{
+    get_bar_fill_style, get_bar_style, get_hud_column_style, get_hud_style, BAR_BACKGROUND_COLOR,
+    HEALTH_BAR_COLOR, STAMINA_BAR_COLOR,
+};
+use crate::ui::widgets::theme::UiTheme;
+use crate::AppState;
+
+pub fn spawn_hud(mut commands: Commands, theme: Res<UiTheme>) {
+    build_hud(&mut commands, &theme);
+}
+
+/// Starts empty, the values come in with the first update.
+pub fn build_hud(commands: &mut Commands, theme: &UiTheme) -> Entity {
+    commands
+        .spawn((
+            NodeBundle {
+                style: get_hud_style(),
+                ..default()
+            },
+            Hud {},
+            StateScoped(AppState::Game),
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn(NodeBundle {
+                    style: get_hud_column_style(),
+                    ..default()
+                })
+                .with_children(|parent| {
+                    build_bar(parent, HudBar::Health, HEALTH_BAR_COLOR);
+                    build_bar(parent, HudBar::Stamina, STAMINA_BAR_COLOR);
+                });
+
+            parent.spawn((
+                TextBundle::from_section("", theme.text_style()),
+                ObjectiveText {},
+            ));
+
+            parent.spawn((
+                TextBundle::from_section("", theme.text_style()),
+                InGameScore {},
+            ));
+        })
+        .id()
+}
+
+fn build_bar(parent: &mut ChildBuilder, bar: HudBar, color: Color) {
+    parent
+        .spawn(NodeBundle {
+            style: get_bar_style(),
+            background_color: BAR_BACKGROUND_COLOR.into(),
+            ..default()
+        })
+        .with_children(|parent| {
+            parent.spawn((
+                NodeBundle {
+                    style: get_bar_fill_style(),
+                    background_color: color.into(),
+                    ..default()
+                },
+                bar,
+            ));
+        });
+}
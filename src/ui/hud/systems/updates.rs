@@ -0,0 +1,56 @@
+use bevy::prelude::{Changed, Query, Res, Style, Text, Val, With};
+
+use crate::game::health::health::Health;
+use crate::game::inventory::inventory::{Inventory, ItemId};
+use crate::game::objective::objective::Objective;
+use crate::game::player::player::Player;
+use crate::game::stamina::stamina::Stamina;
+use crate::ui::hud::components::{HudBar, InGameScore, ObjectiveText};
+
+pub fn update_health_bar(
+    player_query: Query<&Health, (With<Player>, Changed<Health>)>,
+    mut bar_query: Query<(&HudBar, &mut Style)>,
+) {
+    if let Ok(health) = player_query.get_single() {
+        set_bar(&mut bar_query, HudBar::Health, health.fraction());
+    }
+}
+
+pub fn update_stamina_bar(
+    player_query: Query<&Stamina, (With<Player>, Changed<Stamina>)>,
+    mut bar_query: Query<(&HudBar, &mut Style)>,
+) {
+    if let Ok(stamina) = player_query.get_single() {
+        set_bar(&mut bar_query, HudBar::Stamina, stamina.fraction());
+    }
+}
+
+pub fn update_coin_counter(
+    player_query: Query<&Inventory, (With<Player>, Changed<Inventory>)>,
+    mut text_query: Query<&mut Text, With<InGameScore>>,
+) {
+    let Ok(inventory) = player_query.get_single() else {
+        return;
+    };
+
+    for mut text in text_query.iter_mut() {
+        text.sections[0].value = format!("Coins: {}", inventory.count(ItemId::Coin));
+    }
+}
+
+pub fn update_objective_text(
+    objective: Res<Objective>,
+    mut text_query: Query<&mut Text, With<ObjectiveText>>,
+) {
+    for mut text in text_query.iter_mut() {
+        text.sections[0].value = objective.description.clone();
+    }
+}
+
+fn set_bar(bar_query: &mut Query<(&HudBar, &mut Style)>, bar: HudBar, fraction: f32) {
+    for (hud_bar, mut style) in bar_query.iter_mut() {
+        if *hud_bar == bar {
+            style.width = Val::Percent(fraction.clamp(0.0, 1.0) * 100.0);
+        }
+    }
+}
@@ -1,7 +1,4 @@
 use bevy::prelude::Component;
 
-#[derive(Component)]
-pub struct InGameScore;
-
 #[derive(Component)]
 pub struct ControlsHint;
@@ -1,17 +1,161 @@
+use std::collections::HashSet;
+
 use bevy::app::App;
-use bevy::prelude::{OnEnter, OnExit, Plugin};
+use bevy::prelude::{
+    in_state, on_event, GamepadButtonType, IntoSystemConfigs, KeyCode, OnEnter, Plugin, Resource,
+    Update,
+};
+
+use crate::game::controls::controls::{
+    button_glyph, key_glyph, ActionEvent, ControlBindings, ControlledAction, InputDevice,
+};
+use crate::game::game::{GameState, PAUSE_BUTTON, PAUSE_KEY};
+use crate::ui::in_game::systems::interactions::{
+    mark_pause_used, toggle_controls_hint, track_used_actions,
+};
+use crate::ui::in_game::systems::layout::{spawn_controls_hint, update_controls_hint};
 use crate::AppState;
-use crate::ui::in_game::systems::layout::{despwan_controls_hint, spawn_controls_hint};
 
 pub mod components;
+mod styles;
 mod systems;
 
+pub const CONTROLS_HINT_KEY: KeyCode = KeyCode::KeyH;
+pub const CONTROLS_HINT_BUTTON: GamepadButtonType = GamepadButtonType::Select;
+
 pub struct InGameUiPlugin;
 
 impl Plugin for InGameUiPlugin {
     fn build(&self, app: &mut App) {
-        app
+        app.init_resource::<ControlsHintState>()
             .add_systems(OnEnter(AppState::Game), spawn_controls_hint)
-            .add_systems(OnExit(AppState::Game), despwan_controls_hint);
+            .add_systems(OnEnter(GameState::Paused), mark_pause_used)
+            .add_systems(
+                Update,
+                (
+                    track_used_actions.run_if(on_event::<ActionEvent>()),
+                    toggle_controls_hint,
+                    update_controls_hint,
+                )
+                    .chain()
+                    .run_if(in_state(AppState::Game)),
+            );
+    }
+}
+
+/// Whether the controls hint follows what the player has already tried or was toggled by hand.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ControlsHintMode {
+    /// Lists only the actions that were not used yet, hides once all of them were.
+    #[default]
+    Auto,
+    Shown,
+    Hidden,
+}
+
+/// Kept for the whole run of the game, so a new session does not teach the controls again.
+#[derive(Resource, Debug, Default)]
+pub struct ControlsHintState {
+    pub mode: ControlsHintMode,
+    pub used: HashSet<HintAction>,
+}
+
+impl ControlsHintState {
+    /// Actions the hint lists right now, empty when it is hidden.
+    pub fn shown_actions(&self) -> Vec<HintAction> {
+        HintAction::ACTIONS
+            .into_iter()
+            .filter(|action| match self.mode {
+                ControlsHintMode::Auto => !self.used.contains(action),
+                ControlsHintMode::Shown => true,
+                ControlsHintMode::Hidden => false,
+            })
+            .collect()
     }
 }
+
+/// Entry of the controls hint, the four move actions share one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HintAction {
+    Move,
+    Run,
+    Attack,
+    Pause,
+}
+
+impl HintAction {
+    pub const ACTIONS: [Self; 4] = [
+        HintAction::Move,
+        HintAction::Run,
+        HintAction::Attack,
+        HintAction::Pause,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            HintAction::Move => "Move",
+            HintAction::Run => "Run",
+            HintAction::Attack => "Attack",
+            HintAction::Pause => "Pause",
+        }
+    }
+
+    pub fn from_action(action: ControlledAction) -> Option<Self> {
+        match action {
+            ControlledAction::Run => Some(HintAction::Run),
+            ControlledAction::Attack => Some(HintAction::Attack),
+            ControlledAction::None => None,
+            _ if action.is_move_action() => Some(HintAction::Move),
+            _ => None,
+        }
+    }
+
+    /// Keys or buttons of the action on `device`, e.g. `↑/←/↓/→, W/A/S/D` for moving.
+    pub fn glyphs(&self, bindings: &ControlBindings, device: InputDevice) -> String {
+        let glyphs = match self {
+            HintAction::Move => move_glyphs(bindings, device),
+            HintAction::Run => bindings.glyphs_for(ControlledAction::Run, device),
+            HintAction::Attack => bindings.glyphs_for(ControlledAction::Attack, device),
+            HintAction::Pause => match device {
+                InputDevice::Keyboard => vec![key_glyph(&PAUSE_KEY)],
+                InputDevice::Gamepad => vec![button_glyph(&PAUSE_BUTTON)],
+            },
+        };
+
+        if glyphs.is_empty() {
+            "Unbound".to_string()
+        } else {
+            glyphs.join(", ")
+        }
+    }
+}
+
+/// Groups the move bindings into sets of four, one per binding of each direction.
+fn move_glyphs(bindings: &ControlBindings, device: InputDevice) -> Vec<String> {
+    let directions: Vec<Vec<String>> = [
+        ControlledAction::MoveUp,
+        ControlledAction::MoveLeft,
+        ControlledAction::MoveDown,
+        ControlledAction::MoveRight,
+    ]
+    .into_iter()
+    .map(|action| bindings.glyphs_for(action, device))
+    .collect();
+    let set_count = directions.iter().map(Vec::len).min().unwrap_or(0);
+
+    let mut sets: Vec<String> = (0..set_count)
+        .map(|index| {
+            directions
+                .iter()
+                .map(|glyphs| glyphs[index].as_str())
+                .collect::<Vec<&str>>()
+                .join("/")
+        })
+        .collect();
+
+    if device == InputDevice::Gamepad {
+        sets.insert(0, "LS".to_string());
+    }
+
+    sets
+}
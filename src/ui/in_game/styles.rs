@@ -0,0 +1,10 @@
+use bevy::prelude::{PositionType, Style, Val};
+
+pub fn get_controls_hint_style() -> Style {
+    Style {
+        position_type: PositionType::Absolute,
+        left: Val::Px(24.0),
+        bottom: Val::Px(24.0),
+        ..Style::DEFAULT
+    }
+}
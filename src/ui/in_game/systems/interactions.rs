@@ -0,0 +1,51 @@
+use bevy::prelude::{ButtonInput, EventReader, GamepadButton, KeyCode, Res, ResMut};
+
+use crate::game::controls::controls::ActionEvent;
+use crate::ui::in_game::{
+    ControlsHintMode, ControlsHintState, HintAction, CONTROLS_HINT_BUTTON, CONTROLS_HINT_KEY,
+};
+
+pub fn track_used_actions(
+    mut action_reader: EventReader<ActionEvent>,
+    mut hint_state: ResMut<ControlsHintState>,
+) {
+    for event in action_reader.read() {
+        for hint_action in event
+            .actions
+            .iter()
+            .filter_map(|action| HintAction::from_action(*action))
+        {
+            // Checked first, so the hint is not rebuilt on every frame of movement.
+            if !hint_state.used.contains(&hint_action) {
+                hint_state.used.insert(hint_action);
+            }
+        }
+    }
+}
+
+pub fn mark_pause_used(mut hint_state: ResMut<ControlsHintState>) {
+    if !hint_state.used.contains(&HintAction::Pause) {
+        hint_state.used.insert(HintAction::Pause);
+    }
+}
+
+/// Hides the hint when it is showing anything, otherwise shows every action again.
+pub fn toggle_controls_hint(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    gamepad_buttons: Res<ButtonInput<GamepadButton>>,
+    mut hint_state: ResMut<ControlsHintState>,
+) {
+    let button_pressed = gamepad_buttons
+        .get_just_pressed()
+        .any(|button| button.button_type == CONTROLS_HINT_BUTTON);
+
+    if !keyboard_input.just_pressed(CONTROLS_HINT_KEY) && !button_pressed {
+        return;
+    }
+
+    hint_state.mode = if hint_state.shown_actions().is_empty() {
+        ControlsHintMode::Shown
+    } else {
+        ControlsHintMode::Hidden
+    };
+}
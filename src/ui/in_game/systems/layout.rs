@@ -1,37 +1,70 @@
-use bevy::asset::AssetServer;
-use bevy::color::Color;
-use bevy::prelude::{Commands, default, DespawnRecursiveExt, Entity, Query, Res, Text, TextBundle, TextSection, TextStyle, With};
+use bevy::prelude::{
+    Commands, DetectChanges, Entity, Query, Ref, Res, StateScoped, Text, TextBundle, Visibility,
+};
 
+use crate::game::controls::controls::{button_glyph, key_glyph, ControlBindings, InputDevice};
 use crate::ui::in_game::components::ControlsHint;
+use crate::ui::in_game::styles::get_controls_hint_style;
+use crate::ui::in_game::{ControlsHintState, CONTROLS_HINT_BUTTON, CONTROLS_HINT_KEY};
+use crate::ui::widgets::theme::UiTheme;
+use crate::AppState;
 
-pub fn spawn_controls_hint(mut commands: Commands, asset_server: Res<AssetServer>) {
-    build_main_menu(&mut commands, &asset_server);
+pub fn spawn_controls_hint(mut commands: Commands, theme: Res<UiTheme>) {
+    build_controls_hint(&mut commands, &theme);
 }
 
-pub fn despwan_controls_hint(mut commands: Commands, query: Query<Entity, With<ControlsHint>>) {
-    if let Ok(entity) = query.get_single() {
-        commands.entity(entity).despawn_recursive();
-    }
-}
-
-pub fn build_main_menu(commands: &mut Commands, asset_server: &Res<AssetServer>) -> Entity {
-    return commands
+/// Starts empty, the text is generated from the bindings by [`update_controls_hint`].
+pub fn build_controls_hint(commands: &mut Commands, theme: &UiTheme) -> Entity {
+    commands
         .spawn((
             TextBundle {
-                text: Text {
-                    sections: vec![TextSection::new(
-                        "Pause - 'SPACE' Attack - 'F' Main Menu - 'M' Accelerate - 'Shift'",
-                        TextStyle {
-                            font: asset_server.load("fonts/Paint-Peel-Cyr.ttf"),
-                            font_size: 45.0,
-                            color: Color::WHITE,
-                        },
-                    )],
-                    ..default()
-                },
-                ..default()
+                style: get_controls_hint_style(),
+                ..TextBundle::from_section("", theme.text_style())
             },
             ControlsHint {},
+            StateScoped(AppState::Game),
         ))
-        .id();
-}
\ No newline at end of file
+        .id()
+}
+
+pub fn update_controls_hint(
+    hint_state: Res<ControlsHintState>,
+    bindings: Res<ControlBindings>,
+    input_device: Res<InputDevice>,
+    mut hint_query: Query<(Ref<ControlsHint>, &mut Text, &mut Visibility)>,
+) {
+    let inputs_changed =
+        hint_state.is_changed() || bindings.is_changed() || input_device.is_changed();
+
+    for (hint, mut text, mut visibility) in hint_query.iter_mut() {
+        if !inputs_changed && !hint.is_added() {
+            continue;
+        }
+
+        let shown_actions = hint_state.shown_actions();
+
+        if shown_actions.is_empty() {
+            *visibility = Visibility::Hidden;
+            continue;
+        }
+
+        let toggle_glyph = match *input_device {
+            InputDevice::Keyboard => key_glyph(&CONTROLS_HINT_KEY),
+            InputDevice::Gamepad => button_glyph(&CONTROLS_HINT_BUTTON),
+        };
+        let mut entries: Vec<String> = shown_actions
+            .iter()
+            .map(|action| {
+                format!(
+                    "{} [{}]",
+                    action.label(),
+                    action.glyphs(&bindings, *input_device)
+                )
+            })
+            .collect();
+        entries.push(format!("Hide [{}]", toggle_glyph));
+
+        text.sections[0].value = entries.join("   ");
+        *visibility = Visibility::Inherited;
+    }
+}
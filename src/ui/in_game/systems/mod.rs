@@ -1 +1,2 @@
-pub mod layout;
\ No newline at end of file
+pub mod interactions;
+pub mod layout;
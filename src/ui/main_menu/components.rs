@@ -1,10 +1,30 @@
 use bevy::prelude::Component;
 
+use crate::save::save::SaveSlot;
+
 #[derive(Component)]
 pub struct MainMenu;
 
+/// What a main menu button does when pressed.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MainMenuAction {
+    /// Opens the saves to continue, only shown when one exists.
+    Continue,
+    NewGame,
+    /// Starts a new game in the slot, asking first when it holds a save.
+    NewGameIn(SaveSlot),
+    /// Starts a new game in the slot after the overwrite was confirmed.
+    Overwrite(SaveSlot),
+    Load(SaveSlot),
+    Settings,
+    Credits,
+    Quit,
+    /// Leaves the credits or a slot list for the menu.
+    Back,
+}
+
 #[derive(Component)]
-pub struct PlayButton;
+pub struct Credits;
 
 #[derive(Component)]
 pub struct SettingsMenu;
@@ -15,8 +35,5 @@ pub struct VolumeSettings;
 #[derive(Component)]
 pub struct GameSettings;
 
-#[derive(Component)]
-pub struct SettingsButton;
-
 #[derive(Component)]
 pub struct Dimmed;
@@ -1,22 +1,55 @@
-use bevy::prelude::{in_state, App, IntoSystemConfigs, OnEnter, OnExit, Plugin, Update};
+use bevy::prelude::{
+    in_state, on_event, state_changed, App, AppExtStates, IntoSystemConfigs, OnEnter, Plugin,
+    StateSet, SubStates, Update,
+};
 
+use crate::save::save::SaveSlot;
+use crate::ui::widgets::button::ButtonPressedEvent;
 use crate::AppState;
-use systems::interactions::interact_with_play_button;
-use systems::layout::{despwan_main_menu, spawn_main_menu};
+use systems::interactions::handle_main_menu_actions;
+use systems::layout::{
+    spawn_credits, spawn_load_game_slots, spawn_main_menu, spawn_new_game_slots,
+    spawn_overwrite_confirmation,
+};
 
 pub mod components;
-mod styles;
+pub mod styles;
 mod systems;
 
 pub struct MainMenuPlugin;
 
+/// Screen of the main menu, it only exists while in [`AppState::MainMenu`].
+#[derive(SubStates, Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[source(AppState = AppState::MainMenu)]
+pub enum MainMenuState {
+    #[default]
+    Title,
+    Credits,
+    /// Picks the slot a new game saves to.
+    NewGame,
+    /// Picks the save to continue.
+    LoadGame,
+    /// Asks before a new game replaces the save in the slot.
+    ConfirmOverwrite(SaveSlot),
+}
+
 impl Plugin for MainMenuPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(OnEnter(AppState::MainMenu), spawn_main_menu)
-            .add_systems(OnExit(AppState::MainMenu), despwan_main_menu)
+        app.add_sub_state::<MainMenuState>()
+            .enable_state_scoped_entities::<MainMenuState>()
+            .add_systems(OnEnter(MainMenuState::Title), spawn_main_menu)
+            .add_systems(OnEnter(MainMenuState::Credits), spawn_credits)
+            .add_systems(OnEnter(MainMenuState::NewGame), spawn_new_game_slots)
+            .add_systems(OnEnter(MainMenuState::LoadGame), spawn_load_game_slots)
+            .add_systems(
+                Update,
+                spawn_overwrite_confirmation.run_if(state_changed::<MainMenuState>),
+            )
             .add_systems(
                 Update,
-                interact_with_play_button.run_if(in_state(AppState::MainMenu)),
+                handle_main_menu_actions
+                    .run_if(in_state(AppState::MainMenu))
+                    .run_if(on_event::<ButtonPressedEvent>()),
             );
     }
 }
@@ -3,10 +3,6 @@ use bevy::prelude::{
     Val,
 };
 
-pub const NORMAL_BUTTON_COLOR: Color = Color::srgb(0.15, 0.15, 0.15);
-pub const HOVERED_BUTTON_COLOR: Color = Color::srgb(0.25, 0.25, 0.25);
-pub const PRESSED_BUTTON_COLOR: Color = Color::srgb(0.35, 0.75, 0.35);
-
 pub fn get_main_menu_style() -> Style {
     Style {
         flex_direction: FlexDirection::Column,
@@ -20,16 +16,6 @@ pub fn get_main_menu_style() -> Style {
     }
 }
 
-pub fn get_button_style() -> Style {
-    Style {
-        justify_content: JustifyContent::Center,
-        align_items: AlignItems::Center,
-        width: Val::Px(300.0),
-        height: Val::Px(80.0),
-        ..Style::DEFAULT
-    }
-}
-
 pub fn get_main_menu_image_style() -> Style {
     Style {
         width: Val::Percent(100.0),
@@ -67,10 +53,22 @@ pub fn get_title_text_style(asset_server: &Res<AssetServer>) -> TextStyle {
     }
 }
 
-pub fn get_button_text_style(asset_server: &Res<AssetServer>) -> TextStyle {
+pub fn get_credits_style() -> Style {
+    Style {
+        flex_direction: FlexDirection::Column,
+        justify_content: JustifyContent::Center,
+        align_items: AlignItems::Center,
+        width: Val::Percent(100.0),
+        height: Val::Percent(100.0),
+        row_gap: Val::Px(16.0),
+        ..Style::DEFAULT
+    }
+}
+
+pub fn get_credits_text_style(asset_server: &Res<AssetServer>) -> TextStyle {
     TextStyle {
         font: asset_server.load("fonts/Paint-Peel-Cyr.ttf"),
-        font_size: 50.0,
+        font_size: 40.0,
         color: Color::BLACK,
     }
 }
@@ -1,28 +1,52 @@
-use bevy::color::Color;
-use bevy::prelude::{BackgroundColor, Changed, Interaction, NextState, Query, ResMut, With};
+use bevy::prelude::{AppExit, EventReader, EventWriter, NextState, Query, ResMut};
 
-use crate::ui::main_menu::components::PlayButton;
-use crate::ui::main_menu::styles::{HOVERED_BUTTON_COLOR, PRESSED_BUTTON_COLOR};
-use crate::AppState;
+use crate::save::save::{is_slot_used, LoadGameEvent, NewGameEvent};
+use crate::ui::main_menu::components::MainMenuAction;
+use crate::ui::main_menu::MainMenuState;
+use crate::ui::settings::SettingsState;
+use crate::ui::widgets::button::ButtonPressedEvent;
 
-pub fn interact_with_play_button(
-    mut button_query: Query<
-        (&Interaction, &mut BackgroundColor),
-        (Changed<Interaction>, With<PlayButton>),
-    >,
-    mut app_state_next_state: ResMut<NextState<AppState>>,
+pub fn handle_main_menu_actions(
+    mut pressed_reader: EventReader<ButtonPressedEvent>,
+    action_query: Query<&MainMenuAction>,
+    mut main_menu_next_state: ResMut<NextState<MainMenuState>>,
+    mut settings_next_state: ResMut<NextState<SettingsState>>,
+    mut new_game_writer: EventWriter<NewGameEvent>,
+    mut load_game_writer: EventWriter<LoadGameEvent>,
+    mut app_exit_writer: EventWriter<AppExit>,
 ) {
-    if let Ok((interaction, mut background_color)) = button_query.get_single_mut() {
-        match *interaction {
-            Interaction::Pressed => {
-                *background_color = PRESSED_BUTTON_COLOR.into();
-                app_state_next_state.set(AppState::Game);
+    for event in pressed_reader.read() {
+        let Ok(action) = action_query.get(event.entity) else {
+            continue;
+        };
+
+        match *action {
+            MainMenuAction::Continue => {
+                main_menu_next_state.set(MainMenuState::LoadGame);
+            }
+            MainMenuAction::NewGame => {
+                main_menu_next_state.set(MainMenuState::NewGame);
+            }
+            MainMenuAction::NewGameIn(slot) if is_slot_used(slot) => {
+                main_menu_next_state.set(MainMenuState::ConfirmOverwrite(slot));
+            }
+            MainMenuAction::NewGameIn(slot) | MainMenuAction::Overwrite(slot) => {
+                new_game_writer.send(NewGameEvent::new(slot));
+            }
+            MainMenuAction::Load(slot) => {
+                load_game_writer.send(LoadGameEvent::new(slot));
+            }
+            MainMenuAction::Settings => {
+                settings_next_state.set(SettingsState::Controls);
+            }
+            MainMenuAction::Credits => {
+                main_menu_next_state.set(MainMenuState::Credits);
             }
-            Interaction::Hovered => {
-                *background_color = HOVERED_BUTTON_COLOR.into();
+            MainMenuAction::Quit => {
+                app_exit_writer.send(AppExit::Success);
             }
-            Interaction::None => {
-                *background_color = BackgroundColor(Color::NONE);
+            MainMenuAction::Back => {
+                main_menu_next_state.set(MainMenuState::Title);
             }
         }
     }
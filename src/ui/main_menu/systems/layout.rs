@@ -1,24 +1,49 @@
 use bevy::prelude::{
-    default, AssetServer, BackgroundColor, BuildChildren, ButtonBundle, Color, Commands,
-    DespawnRecursiveExt, Entity, ImageBundle, Query, Res, Text, TextBundle, TextSection, UiImage,
-    With,
+    default, AssetServer, BuildChildren, ChildBuilder, Color, Commands, Entity, ImageBundle, Res,
+    State, StateScoped, TextBundle, UiImage, Vec2,
 };
 
-use crate::ui::main_menu::components::{MainMenu, PlayButton};
-use crate::ui::main_menu::styles::{get_button_style, get_button_text_style, get_main_menu_style};
+use crate::save::save::{is_slot_used, latest_save_slot, read_save, SaveSlot, SAVE_SLOTS};
+use crate::ui::main_menu::components::{Credits, MainMenu, MainMenuAction};
+use crate::ui::main_menu::styles::{
+    get_credits_style, get_credits_text_style, get_main_menu_style, get_title_text_style,
+};
+use crate::ui::main_menu::MainMenuState;
+use crate::ui::widgets::button::ButtonWidget;
+use crate::ui::widgets::focus::FocusLayer;
+use crate::ui::widgets::theme::UiTheme;
 
-pub fn spawn_main_menu(mut commands: Commands, asset_server: Res<AssetServer>) {
-    build_main_menu(&mut commands, &asset_server);
-}
+/// PLACEHOLDER until the maintainers supply the real credits. Still to be attributed:
+/// the character, location and item sprites, the menu art, the FiraSans and Paint Peel
+/// fonts and every sound effect in `assets/audio`. The music is synthesized placeholders.
+const CREDITS: [&str; 4] = [
+    "[Placeholder credits]",
+    "Authors: to be supplied by the maintainers",
+    "Third-party art, fonts and sounds: attribution pending",
+    "Made with Bevy",
+];
+
+/// Text of the buttons drawn over the bright menu art.
+const MENU_TEXT_COLOR: Color = Color::BLACK;
+/// Wide enough for the slot number and the location of its save.
+const SLOT_BUTTON_SIZE: Vec2 = Vec2::new(720.0, 80.0);
 
-pub fn despwan_main_menu(mut commands: Commands, main_menu_query: Query<Entity, With<MainMenu>>) {
-    if let Ok(main_menu_entity) = main_menu_query.get_single() {
-        commands.entity(main_menu_entity).despawn_recursive();
-    }
+pub fn spawn_main_menu(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    theme: Res<UiTheme>,
+) {
+    build_main_menu(&mut commands, &asset_server, &theme);
 }
 
-pub fn build_main_menu(commands: &mut Commands, asset_server: &Res<AssetServer>) -> Entity {
-    return commands
+pub fn build_main_menu(
+    commands: &mut Commands,
+    asset_server: &Res<AssetServer>,
+    theme: &UiTheme,
+) -> Entity {
+    let menu_button = |title: &str| ButtonWidget::new(title).with_text_color(MENU_TEXT_COLOR);
+
+    commands
         .spawn((
             ImageBundle {
                 style: get_main_menu_style(),
@@ -26,29 +51,180 @@ pub fn build_main_menu(commands: &mut Commands, asset_server: &Res<AssetServer>)
                 ..default()
             },
             MainMenu {},
+            FocusLayer::new(0),
+            StateScoped(MainMenuState::Title),
         ))
         .with_children(|parent| {
-            parent
-                .spawn((
-                    ButtonBundle {
-                        style: get_button_style(),
-                        background_color: BackgroundColor(Color::NONE),
-                        ..default()
-                    },
-                    PlayButton {},
-                ))
-                .with_children(|parent| {
-                    parent.spawn(TextBundle {
-                        text: Text {
-                            sections: vec![TextSection::new(
-                                "NEW GAME",
-                                get_button_text_style(&asset_server),
-                            )],
-                            ..default()
-                        },
-                        ..default()
-                    });
-                });
+            if latest_save_slot().is_some() {
+                menu_button("CONTINUE").spawn(parent, theme, MainMenuAction::Continue);
+            }
+
+            menu_button("NEW GAME").spawn(parent, theme, MainMenuAction::NewGame);
+            ButtonWidget::image(asset_server.load("images/menu/settings_button.png")).spawn(
+                parent,
+                theme,
+                MainMenuAction::Settings,
+            );
+            menu_button("CREDITS").spawn(parent, theme, MainMenuAction::Credits);
+            menu_button("QUIT").spawn(parent, theme, MainMenuAction::Quit);
         })
-        .id();
+        .id()
+}
+
+pub fn spawn_credits(mut commands: Commands, asset_server: Res<AssetServer>, theme: Res<UiTheme>) {
+    commands
+        .spawn((
+            ImageBundle {
+                style: get_credits_style(),
+                image: UiImage::new(asset_server.load("images/menu/main_menu.png")),
+                ..default()
+            },
+            Credits {},
+            FocusLayer::new(0),
+            StateScoped(MainMenuState::Credits),
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(
+                "CREDITS",
+                get_title_text_style(&asset_server),
+            ));
+
+            for line in CREDITS {
+                parent.spawn(TextBundle::from_section(
+                    line,
+                    get_credits_text_style(&asset_server),
+                ));
+            }
+
+            back_button(parent, &theme, MainMenuAction::Back);
+        });
+}
+
+pub fn spawn_new_game_slots(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    theme: Res<UiTheme>,
+) {
+    build_menu_screen(
+        &mut commands,
+        &asset_server,
+        "NEW GAME",
+        MainMenuState::NewGame,
+        |parent| {
+            for slot in (0..SAVE_SLOTS).map(SaveSlot) {
+                slot_button(slot_label(slot)).spawn(
+                    parent,
+                    &theme,
+                    MainMenuAction::NewGameIn(slot),
+                );
+            }
+
+            back_button(parent, &theme, MainMenuAction::Back);
+        },
+    );
+}
+
+/// Lists the saves that can be loaded, empty and unreadable slots are left out.
+pub fn spawn_load_game_slots(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    theme: Res<UiTheme>,
+) {
+    build_menu_screen(
+        &mut commands,
+        &asset_server,
+        "CONTINUE",
+        MainMenuState::LoadGame,
+        |parent| {
+            for slot in (0..SAVE_SLOTS).map(SaveSlot) {
+                if read_save(slot).is_ok() {
+                    slot_button(slot_label(slot)).spawn(parent, &theme, MainMenuAction::Load(slot));
+                }
+            }
+
+            back_button(parent, &theme, MainMenuAction::Back);
+        },
+    );
+}
+
+/// The confirmation carries its slot, so it is spawned on any change to it rather than on
+/// entering one fixed state.
+pub fn spawn_overwrite_confirmation(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    theme: Res<UiTheme>,
+    main_menu_state: Res<State<MainMenuState>>,
+) {
+    let MainMenuState::ConfirmOverwrite(slot) = *main_menu_state.get() else {
+        return;
+    };
+
+    build_menu_screen(
+        &mut commands,
+        &asset_server,
+        &format!("OVERWRITE SLOT {}?", slot.0 + 1),
+        MainMenuState::ConfirmOverwrite(slot),
+        |parent| {
+            parent.spawn(TextBundle::from_section(
+                "The saved game will be lost",
+                get_credits_text_style(&asset_server),
+            ));
+
+            slot_button("YES, START OVER").spawn(parent, &theme, MainMenuAction::Overwrite(slot));
+            back_button(parent, &theme, MainMenuAction::NewGame);
+        },
+    );
+}
+
+/// Screen over the menu art with a title, living as long as `state`.
+fn build_menu_screen(
+    commands: &mut Commands,
+    asset_server: &Res<AssetServer>,
+    title: &str,
+    state: MainMenuState,
+    spawn_content: impl FnOnce(&mut ChildBuilder),
+) {
+    commands
+        .spawn((
+            ImageBundle {
+                style: get_credits_style(),
+                image: UiImage::new(asset_server.load("images/menu/main_menu.png")),
+                ..default()
+            },
+            FocusLayer::new(0),
+            StateScoped(state),
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(
+                title,
+                get_title_text_style(asset_server),
+            ));
+            spawn_content(parent);
+        });
+}
+
+fn slot_button(title: impl Into<String>) -> ButtonWidget {
+    ButtonWidget::new(title)
+        .with_size(SLOT_BUTTON_SIZE)
+        .with_text_color(MENU_TEXT_COLOR)
+}
+
+fn back_button(parent: &mut ChildBuilder, theme: &UiTheme, action: MainMenuAction) {
+    ButtonWidget::new("BACK")
+        .with_text_color(MENU_TEXT_COLOR)
+        .back()
+        .spawn(parent, theme, action);
+}
+
+fn slot_label(slot: SaveSlot) -> String {
+    let content = if !is_slot_used(slot) {
+        "EMPTY".to_string()
+    } else {
+        match read_save(slot) {
+            Ok(save) => save.player.location.label().to_uppercase(),
+            Err(_) => "UNREADABLE SAVE".to_string(),
+        }
+    };
+
+    format!("SLOT {} - {}", slot.0 + 1, content)
 }
@@ -1,15 +1,30 @@
 use crate::ui::main_menu::MainMenuPlugin;
 use bevy::app::App;
 use bevy::prelude::Plugin;
+use crate::ui::hud::HudPlugin;
 use crate::ui::in_game::InGameUiPlugin;
+use crate::ui::pause_menu::PauseMenuPlugin;
+use crate::ui::settings::SettingsMenuPlugin;
+use crate::ui::widgets::WidgetsPlugin;
 
+pub mod hud;
 pub mod in_game;
 pub mod main_menu;
+pub mod pause_menu;
+pub mod settings;
+pub mod widgets;
 
 pub struct UiPlugin;
 
 impl Plugin for UiPlugin {
     fn build(&self, app: &mut App) {
-        app.add_plugins((MainMenuPlugin, InGameUiPlugin));
+        app.add_plugins((
+            MainMenuPlugin,
+            HudPlugin,
+            InGameUiPlugin,
+            PauseMenuPlugin,
+            SettingsMenuPlugin,
+            WidgetsPlugin,
+        ));
     }
 }
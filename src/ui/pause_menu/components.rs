@@ -0,0 +1,13 @@
+use bevy::prelude::Component;
+
+#[derive(Component)]
+pub struct PauseMenu;
+
+/// What a pause menu button does when pressed.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PauseMenuAction {
+    Resume,
+    Settings,
+    MainMenu,
+    Quit,
+}
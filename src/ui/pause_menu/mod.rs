@@ -0,0 +1,26 @@
+use bevy::prelude::{in_state, on_event, App, IntoSystemConfigs, OnEnter, Plugin, Update};
+
+use crate::game::game::GameState;
+use crate::ui::settings::SettingsState;
+use crate::ui::widgets::button::ButtonPressedEvent;
+use systems::interactions::handle_pause_menu_actions;
+use systems::layout::spawn_pause_menu;
+
+pub mod components;
+mod styles;
+mod systems;
+
+pub struct PauseMenuPlugin;
+
+impl Plugin for PauseMenuPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(GameState::Paused), spawn_pause_menu)
+            .add_systems(
+                Update,
+                handle_pause_menu_actions
+                    .run_if(in_state(GameState::Paused))
+                    .run_if(in_state(SettingsState::Closed))
+                    .run_if(on_event::<ButtonPressedEvent>()),
+            );
+    }
+}
@@ -0,0 +1,15 @@
+use bevy::prelude::{AlignItems, Color, FlexDirection, JustifyContent, Style, Val};
+
+pub const PAUSE_BACKGROUND_COLOR: Color = Color::srgba(0.0, 0.0, 0.0, 0.6);
+
+pub fn get_pause_menu_style() -> Style {
+    Style {
+        flex_direction: FlexDirection::Column,
+        justify_content: JustifyContent::Center,
+        align_items: AlignItems::Center,
+        width: Val::Percent(100.0),
+        height: Val::Percent(100.0),
+        row_gap: Val::Px(8.0),
+        ..Style::DEFAULT
+    }
+}
@@ -0,0 +1,39 @@
+use bevy::prelude::{info, AppExit, EventReader, EventWriter, NextState, Query, ResMut};
+
+use crate::game::game::GameState;
+use crate::ui::pause_menu::components::PauseMenuAction;
+use crate::ui::settings::SettingsState;
+use crate::ui::widgets::button::ButtonPressedEvent;
+use crate::AppState;
+
+pub fn handle_pause_menu_actions(
+    mut pressed_reader: EventReader<ButtonPressedEvent>,
+    action_query: Query<&PauseMenuAction>,
+    mut game_state_next_state: ResMut<NextState<GameState>>,
+    mut app_state_next_state: ResMut<NextState<AppState>>,
+    mut settings_next_state: ResMut<NextState<SettingsState>>,
+    mut app_exit_writer: EventWriter<AppExit>,
+) {
+    for event in pressed_reader.read() {
+        let Ok(action) = action_query.get(event.entity) else {
+            continue;
+        };
+
+        match *action {
+            PauseMenuAction::Resume => {
+                game_state_next_state.set(GameState::Running);
+                info!("Game resumed")
+            }
+            PauseMenuAction::Settings => {
+                settings_next_state.set(SettingsState::Controls);
+            }
+            PauseMenuAction::MainMenu => {
+                app_state_next_state.set(AppState::MainMenu);
+                info!("Returned to Main Menu")
+            }
+            PauseMenuAction::Quit => {
+                app_exit_writer.send(AppExit::Success);
+            }
+        }
+    }
+}
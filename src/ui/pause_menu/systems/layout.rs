@@ -0,0 +1,45 @@
+use bevy::prelude::{
+    default, BackgroundColor, BuildChildren, Commands, Entity, NodeBundle, Res, StateScoped,
+    TextBundle, ZIndex,
+};
+use bevy::ui::FocusPolicy;
+
+use crate::game::game::GameState;
+use crate::ui::pause_menu::components::{PauseMenu, PauseMenuAction};
+use crate::ui::pause_menu::styles::{get_pause_menu_style, PAUSE_BACKGROUND_COLOR};
+use crate::ui::widgets::button::ButtonWidget;
+use crate::ui::widgets::focus::FocusLayer;
+use crate::ui::widgets::theme::UiTheme;
+
+pub fn spawn_pause_menu(mut commands: Commands, theme: Res<UiTheme>) {
+    build_pause_menu(&mut commands, &theme);
+}
+
+pub fn build_pause_menu(commands: &mut Commands, theme: &UiTheme) -> Entity {
+    commands
+        .spawn((
+            NodeBundle {
+                style: get_pause_menu_style(),
+                background_color: BackgroundColor(PAUSE_BACKGROUND_COLOR),
+                focus_policy: FocusPolicy::Block,
+                // Below the settings overlay, which opens on top of the pause menu.
+                z_index: ZIndex::Global(5),
+                ..default()
+            },
+            PauseMenu {},
+            FocusLayer::new(5),
+            StateScoped(GameState::Paused),
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section("PAUSED", theme.title_text_style()));
+            for (title, action) in [
+                ("RESUME", PauseMenuAction::Resume),
+                ("SETTINGS", PauseMenuAction::Settings),
+                ("MAIN MENU", PauseMenuAction::MainMenu),
+                ("QUIT", PauseMenuAction::Quit),
+            ] {
+                ButtonWidget::new(title).spawn(parent, theme, action);
+            }
+        })
+        .id()
+}
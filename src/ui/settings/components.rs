@@ -0,0 +1,30 @@
+use bevy::prelude::Component;
+
+use crate::audio::audio::AudioChannel;
+use crate::ui::settings::SettingsState;
+use crate::video::video::VideoOption;
+
+#[derive(Component)]
+pub struct SettingsContent;
+
+#[derive(Component)]
+pub struct ControlsSettings;
+
+#[derive(Component)]
+pub struct SettingsTabButton(pub SettingsState);
+
+#[derive(Component)]
+pub struct SettingsBackButton;
+
+#[derive(Component)]
+pub struct RebindStatusText;
+
+#[derive(Component)]
+pub struct VolumeSlider(pub AudioChannel);
+
+#[derive(Component)]
+pub struct VolumeText(pub AudioChannel);
+
+/// Widget changing one of the video options.
+#[derive(Component)]
+pub struct VideoOptionWidget(pub VideoOption);
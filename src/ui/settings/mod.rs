@@ -0,0 +1,79 @@
+use bevy::prelude::{
+    in_state, not, on_event, resource_changed, state_changed, App, AppExtStates, ComputedStates,
+    Condition, IntoSystemConfigs, OnEnter, OnExit, Plugin, States, Update,
+};
+
+use crate::audio::audio::AudioVolumes;
+use crate::game::controls::controls::RebindEvent;
+use crate::ui::widgets::button::ButtonPressedEvent;
+use crate::ui::widgets::dropdown::DropdownChangedEvent;
+use crate::ui::widgets::slider::SliderChangedEvent;
+use crate::ui::widgets::toggle::ToggleChangedEvent;
+use crate::AppState;
+use systems::interactions::{
+    apply_video_options, apply_volume_sliders, close_settings, handle_settings_buttons,
+};
+use systems::layout::{
+    highlight_selected_tab, spawn_audio_settings, spawn_controls_settings, spawn_settings_menu,
+    spawn_video_settings, update_rebind_status, update_volume_texts,
+};
+
+pub mod components;
+mod styles;
+mod systems;
+
+pub struct SettingsMenuPlugin;
+
+/// Settings overlay, opened on top of whatever screen is showing with the tab that is active.
+#[derive(States, Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum SettingsState {
+    #[default]
+    Closed,
+    Controls,
+    Audio,
+    Video,
+}
+
+/// Exists while the settings overlay is showing, whichever tab is active. The overlay
+/// is scoped to it so switching tabs keeps it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SettingsOpen;
+
+impl ComputedStates for SettingsOpen {
+    type SourceStates = SettingsState;
+
+    fn compute(settings_state: SettingsState) -> Option<Self> {
+        (settings_state != SettingsState::Closed).then_some(SettingsOpen)
+    }
+}
+
+impl Plugin for SettingsMenuPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_state::<SettingsState>()
+            .add_computed_state::<SettingsOpen>()
+            .enable_state_scoped_entities::<SettingsState>()
+            .enable_state_scoped_entities::<SettingsOpen>()
+            // Exit schedules run before any enter one, so the tab opened along with the
+            // overlay finds it already spawned.
+            .add_systems(OnExit(SettingsState::Closed), spawn_settings_menu)
+            .add_systems(OnEnter(SettingsState::Controls), spawn_controls_settings)
+            .add_systems(OnEnter(SettingsState::Audio), spawn_audio_settings)
+            .add_systems(OnEnter(SettingsState::Video), spawn_video_settings)
+            .add_systems(
+                Update,
+                (
+                    handle_settings_buttons.run_if(on_event::<ButtonPressedEvent>()),
+                    apply_volume_sliders.run_if(on_event::<SliderChangedEvent>()),
+                    apply_video_options.run_if(
+                        on_event::<DropdownChangedEvent>()
+                            .or_else(on_event::<ToggleChangedEvent>()),
+                    ),
+                    highlight_selected_tab.run_if(state_changed::<SettingsState>),
+                    update_rebind_status.run_if(on_event::<RebindEvent>()),
+                    update_volume_texts.run_if(resource_changed::<AudioVolumes>),
+                    close_settings.run_if(state_changed::<AppState>),
+                )
+                    .run_if(not(in_state(SettingsState::Closed))),
+            );
+    }
+}
@@ -0,0 +1,56 @@
+use bevy::prelude::{AlignItems, Color, FlexDirection, JustifyContent, Style, UiRect, Val, Vec2};
+
+pub const SETTINGS_BACKGROUND_COLOR: Color = Color::srgba(0.0, 0.0, 0.0, 0.85);
+pub const TAB_BUTTON_SIZE: Vec2 = Vec2::new(240.0, 64.0);
+
+pub fn get_settings_menu_style() -> Style {
+    Style {
+        flex_direction: FlexDirection::Column,
+        justify_content: JustifyContent::Center,
+        align_items: AlignItems::Center,
+        width: Val::Percent(100.0),
+        height: Val::Percent(100.0),
+        row_gap: Val::Px(16.0),
+        ..Style::DEFAULT
+    }
+}
+
+pub fn get_tab_bar_style() -> Style {
+    Style {
+        flex_direction: FlexDirection::Row,
+        justify_content: JustifyContent::Center,
+        align_items: AlignItems::Center,
+        column_gap: Val::Px(8.0),
+        ..Style::DEFAULT
+    }
+}
+
+pub fn get_settings_content_style() -> Style {
+    Style {
+        flex_direction: FlexDirection::Column,
+        align_items: AlignItems::Stretch,
+        width: Val::Px(900.0),
+        min_height: Val::Px(520.0),
+        row_gap: Val::Px(8.0),
+        padding: UiRect::all(Val::Px(16.0)),
+        ..Style::DEFAULT
+    }
+}
+
+pub fn get_settings_row_style() -> Style {
+    Style {
+        flex_direction: FlexDirection::Row,
+        justify_content: JustifyContent::SpaceBetween,
+        align_items: AlignItems::Center,
+        column_gap: Val::Px(16.0),
+        height: Val::Px(56.0),
+        ..Style::DEFAULT
+    }
+}
+
+pub fn get_settings_label_style() -> Style {
+    Style {
+        width: Val::Px(300.0),
+        ..Style::DEFAULT
+    }
+}
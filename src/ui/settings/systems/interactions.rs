@@ -0,0 +1,95 @@
+use bevy::prelude::{error, EventReader, NextState, Query, ResMut};
+
+use crate::audio::audio::AudioVolumes;
+use crate::ui::settings::components::{
+    SettingsBackButton, SettingsTabButton, VideoOptionWidget, VolumeSlider,
+};
+use crate::ui::settings::SettingsState;
+use crate::ui::widgets::button::ButtonPressedEvent;
+use crate::ui::widgets::dropdown::DropdownChangedEvent;
+use crate::ui::widgets::slider::SliderChangedEvent;
+use crate::ui::widgets::toggle::ToggleChangedEvent;
+use crate::video::video::{VideoOption, VideoSettings, WindowModeSetting, RESOLUTIONS};
+
+pub fn handle_settings_buttons(
+    mut pressed_reader: EventReader<ButtonPressedEvent>,
+    tab_query: Query<&SettingsTabButton>,
+    back_query: Query<&SettingsBackButton>,
+    mut settings_next_state: ResMut<NextState<SettingsState>>,
+) {
+    for event in pressed_reader.read() {
+        if let Ok(tab_button) = tab_query.get(event.entity) {
+            settings_next_state.set(tab_button.0);
+        } else if back_query.contains(event.entity) {
+            settings_next_state.set(SettingsState::Closed);
+        }
+    }
+}
+
+/// The overlay belongs to the screen it was opened on, leaving that screen closes it.
+pub fn close_settings(mut settings_next_state: ResMut<NextState<SettingsState>>) {
+    settings_next_state.set(SettingsState::Closed);
+}
+
+pub fn apply_volume_sliders(
+    mut slider_reader: EventReader<SliderChangedEvent>,
+    slider_query: Query<&VolumeSlider>,
+    mut volumes: ResMut<AudioVolumes>,
+) {
+    for event in slider_reader.read() {
+        let Ok(volume_slider) = slider_query.get(event.entity) else {
+            continue;
+        };
+
+        volumes.set_channel(volume_slider.0, event.value);
+
+        if let Err(save_error) = volumes.save() {
+            error!("Failed to save audio volumes: {}", save_error);
+        }
+    }
+}
+
+pub fn apply_video_options(
+    mut dropdown_reader: EventReader<DropdownChangedEvent>,
+    mut toggle_reader: EventReader<ToggleChangedEvent>,
+    option_query: Query<&VideoOptionWidget>,
+    mut video_settings: ResMut<VideoSettings>,
+) {
+    let mut changed = false;
+
+    for event in dropdown_reader.read() {
+        let Ok(option_widget) = option_query.get(event.entity) else {
+            continue;
+        };
+
+        match option_widget.0 {
+            VideoOption::WindowMode => {
+                video_settings.window_mode = WindowModeSetting::MODES[event.selected];
+            }
+            VideoOption::Resolution => {
+                video_settings.resolution = RESOLUTIONS[event.selected];
+            }
+            VideoOption::Vsync | VideoOption::PixelPerfect => continue,
+        }
+        changed = true;
+    }
+
+    for event in toggle_reader.read() {
+        let Ok(option_widget) = option_query.get(event.entity) else {
+            continue;
+        };
+
+        match option_widget.0 {
+            VideoOption::Vsync => video_settings.vsync = event.value,
+            VideoOption::PixelPerfect => video_settings.pixel_perfect = event.value,
+            VideoOption::WindowMode | VideoOption::Resolution => continue,
+        }
+        changed = true;
+    }
+
+    if changed {
+        if let Err(save_error) = video_settings.save() {
+            error!("Failed to save video settings: {}", save_error);
+        }
+    }
+}
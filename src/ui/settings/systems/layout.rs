@@ -0,0 +1,313 @@
+use bevy::prelude::{
+    default, BackgroundColor, BuildChildren, ChildBuilder, Commands, Entity, EventReader,
+    NodeBundle, Query, Res, State, StateScoped, Text, TextBundle, With, ZIndex,
+};
+use bevy::ui::FocusPolicy;
+
+use crate::audio::audio::{AudioChannel, AudioVolumes};
+use crate::game::controls::controls::{
+    key_label, ControlBindings, ControlledAction, PendingRebind, RebindEvent, KEY_SLOTS,
+};
+use crate::ui::main_menu::components::{GameSettings, SettingsMenu, VolumeSettings};
+use crate::ui::settings::components::{
+    ControlsSettings, RebindStatusText, SettingsBackButton, SettingsContent, SettingsTabButton,
+    VideoOptionWidget, VolumeSlider, VolumeText,
+};
+use crate::ui::settings::styles::{
+    get_settings_content_style, get_settings_label_style, get_settings_menu_style,
+    get_settings_row_style, get_tab_bar_style, SETTINGS_BACKGROUND_COLOR, TAB_BUTTON_SIZE,
+};
+use crate::ui::settings::{SettingsOpen, SettingsState};
+use crate::ui::widgets::button::ButtonWidget;
+use crate::ui::widgets::dropdown::DropdownWidget;
+use crate::ui::widgets::focus::{FocusLayer, Focusable};
+use crate::ui::widgets::key_binding::KeyBindingWidget;
+use crate::ui::widgets::slider::SliderWidget;
+use crate::ui::widgets::theme::UiTheme;
+use crate::ui::widgets::toggle::ToggleWidget;
+use crate::video::video::{VideoOption, VideoSettings, WindowModeSetting, RESOLUTIONS};
+
+const VOLUME_STEP: f32 = 0.05;
+
+const TABS: [(SettingsState, &str); 3] = [
+    (SettingsState::Controls, "CONTROLS"),
+    (SettingsState::Audio, "AUDIO"),
+    (SettingsState::Video, "VIDEO"),
+];
+
+pub fn spawn_settings_menu(mut commands: Commands, theme: Res<UiTheme>) {
+    build_settings_menu(&mut commands, &theme);
+}
+
+pub fn build_settings_menu(commands: &mut Commands, theme: &UiTheme) -> Entity {
+    commands
+        .spawn((
+            NodeBundle {
+                style: get_settings_menu_style(),
+                background_color: BackgroundColor(SETTINGS_BACKGROUND_COLOR),
+                // Keeps the screen below from reacting to clicks while settings are open.
+                focus_policy: FocusPolicy::Block,
+                z_index: ZIndex::Global(10),
+                ..default()
+            },
+            SettingsMenu {},
+            FocusLayer::new(10),
+            StateScoped(SettingsOpen),
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(
+                "SETTINGS",
+                theme.title_text_style(),
+            ));
+
+            parent
+                .spawn(NodeBundle {
+                    style: get_tab_bar_style(),
+                    ..default()
+                })
+                .with_children(|parent| {
+                    for (tab, title) in TABS {
+                        ButtonWidget::new(title).with_size(TAB_BUTTON_SIZE).spawn(
+                            parent,
+                            theme,
+                            SettingsTabButton(tab),
+                        );
+                    }
+                });
+
+            parent.spawn((
+                NodeBundle {
+                    style: get_settings_content_style(),
+                    ..default()
+                },
+                SettingsContent {},
+            ));
+
+            ButtonWidget::new("BACK")
+                .with_size(TAB_BUTTON_SIZE)
+                .back()
+                .spawn(parent, theme, SettingsBackButton {});
+        })
+        .id()
+}
+
+pub fn spawn_controls_settings(
+    mut commands: Commands,
+    theme: Res<UiTheme>,
+    bindings: Res<ControlBindings>,
+    pending_rebind: Res<PendingRebind>,
+    content_query: Query<Entity, With<SettingsContent>>,
+) {
+    let Ok(content_entity) = content_query.get_single() else {
+        return;
+    };
+
+    commands.entity(content_entity).with_children(|parent| {
+        parent
+            .spawn((
+                NodeBundle {
+                    style: get_settings_content_style(),
+                    ..default()
+                },
+                ControlsSettings {},
+                StateScoped(SettingsState::Controls),
+            ))
+            .with_children(|parent| {
+                for action in ControlledAction::BINDABLE_ACTIONS {
+                    build_settings_row(parent, &theme, action.label(), |parent| {
+                        for slot in 0..KEY_SLOTS {
+                            KeyBindingWidget::new(action, slot).spawn(
+                                parent,
+                                &theme,
+                                &bindings,
+                                &pending_rebind,
+                            );
+                        }
+                    });
+                }
+
+                parent.spawn((
+                    TextBundle::from_section("", theme.text_style()),
+                    RebindStatusText {},
+                ));
+            });
+    });
+}
+
+pub fn spawn_audio_settings(
+    mut commands: Commands,
+    theme: Res<UiTheme>,
+    volumes: Res<AudioVolumes>,
+    content_query: Query<Entity, With<SettingsContent>>,
+) {
+    let Ok(content_entity) = content_query.get_single() else {
+        return;
+    };
+
+    commands.entity(content_entity).with_children(|parent| {
+        parent
+            .spawn((
+                NodeBundle {
+                    style: get_settings_content_style(),
+                    ..default()
+                },
+                VolumeSettings {},
+                StateScoped(SettingsState::Audio),
+            ))
+            .with_children(|parent| {
+                for channel in AudioChannel::CHANNELS {
+                    build_settings_row(parent, &theme, channel.label(), |parent| {
+                        SliderWidget::new(volumes.channel(channel))
+                            .with_step(VOLUME_STEP)
+                            .spawn(parent, &theme, VolumeSlider(channel));
+                        parent.spawn((
+                            TextBundle::from_section(
+                                volume_label(&volumes, channel),
+                                theme.text_style(),
+                            ),
+                            VolumeText(channel),
+                        ));
+                    });
+                }
+            });
+    });
+}
+
+pub fn spawn_video_settings(
+    mut commands: Commands,
+    theme: Res<UiTheme>,
+    video_settings: Res<VideoSettings>,
+    content_query: Query<Entity, With<SettingsContent>>,
+) {
+    let Ok(content_entity) = content_query.get_single() else {
+        return;
+    };
+
+    commands.entity(content_entity).with_children(|parent| {
+        parent
+            .spawn((
+                NodeBundle {
+                    style: get_settings_content_style(),
+                    ..default()
+                },
+                GameSettings {},
+                StateScoped(SettingsState::Video),
+            ))
+            .with_children(|parent| {
+                for option in VideoOption::OPTIONS {
+                    build_settings_row(parent, &theme, option.label(), |parent| {
+                        spawn_video_option(parent, &theme, &video_settings, option);
+                    });
+                }
+            });
+    });
+}
+
+fn spawn_video_option(
+    parent: &mut ChildBuilder,
+    theme: &UiTheme,
+    video_settings: &VideoSettings,
+    option: VideoOption,
+) {
+    let marker = VideoOptionWidget(option);
+
+    match option {
+        VideoOption::WindowMode => {
+            let modes = WindowModeSetting::MODES;
+            let selected = modes
+                .iter()
+                .position(|mode| *mode == video_settings.window_mode)
+                .unwrap_or_default();
+            let labels = modes.iter().map(|mode| mode.label().to_string()).collect();
+            DropdownWidget::new(labels, selected).spawn(parent, theme, marker);
+        }
+        VideoOption::Resolution => {
+            let selected = RESOLUTIONS
+                .iter()
+                .position(|resolution| *resolution == video_settings.resolution)
+                .unwrap_or_default();
+            let labels = RESOLUTIONS
+                .iter()
+                .map(|resolution| format!("{}x{}", resolution.x, resolution.y))
+                .collect();
+            DropdownWidget::new(labels, selected).spawn(parent, theme, marker);
+        }
+        VideoOption::Vsync => {
+            ToggleWidget::new(video_settings.vsync).spawn(parent, theme, marker);
+        }
+        VideoOption::PixelPerfect => {
+            ToggleWidget::new(video_settings.pixel_perfect).spawn(parent, theme, marker);
+        }
+    }
+}
+
+/// Row of a settings tab: a label followed by whatever `spawn_widgets` adds.
+fn build_settings_row(
+    parent: &mut ChildBuilder,
+    theme: &UiTheme,
+    label: &str,
+    spawn_widgets: impl FnOnce(&mut ChildBuilder),
+) {
+    parent
+        .spawn(NodeBundle {
+            style: get_settings_row_style(),
+            ..default()
+        })
+        .with_children(|parent| {
+            parent.spawn(
+                TextBundle::from_section(label, theme.text_style())
+                    .with_style(get_settings_label_style()),
+            );
+            spawn_widgets(parent);
+        });
+}
+
+pub fn highlight_selected_tab(
+    settings_state: Res<State<SettingsState>>,
+    mut tab_query: Query<(&SettingsTabButton, &mut Focusable)>,
+) {
+    for (tab_button, mut focusable) in tab_query.iter_mut() {
+        focusable.selected = tab_button.0 == *settings_state.get();
+    }
+}
+
+pub fn update_rebind_status(
+    mut rebind_event_reader: EventReader<RebindEvent>,
+    mut text_query: Query<&mut Text, With<RebindStatusText>>,
+) {
+    let Ok(mut text) = text_query.get_single_mut() else {
+        return;
+    };
+
+    for rebind_event in rebind_event_reader.read() {
+        text.sections[0].value = match *rebind_event {
+            RebindEvent::Bound { action, key } => {
+                format!("{} bound to {}", action.label(), key_label(&key))
+            }
+            RebindEvent::Conflict { key, existing, .. } => {
+                format!(
+                    "{} is already used by {}",
+                    key_label(&key),
+                    existing.label()
+                )
+            }
+            RebindEvent::Reserved { key, usage, .. } => {
+                format!("{} is reserved for {}", key_label(&key), usage)
+            }
+            RebindEvent::Cancelled { .. } => "Rebinding cancelled".to_string(),
+        };
+    }
+}
+
+pub fn update_volume_texts(
+    volumes: Res<AudioVolumes>,
+    mut text_query: Query<(&mut Text, &VolumeText)>,
+) {
+    for (mut text, volume_text) in text_query.iter_mut() {
+        text.sections[0].value = volume_label(&volumes, volume_text.0);
+    }
+}
+
+fn volume_label(volumes: &AudioVolumes, channel: AudioChannel) -> String {
+    format!("{:.0}%", volumes.channel(channel) * 100.0)
+}
@@ -0,0 +1,141 @@
+use bevy::prelude::{
+    default, BuildChildren, ButtonBundle, ChildBuilder, Color, Component, Entity, Event,
+    EventReader, EventWriter, Handle, Has, Image, Query, TextBundle, UiImage, Vec2, With,
+};
+
+use crate::audio::audio::{PlaySfxEvent, SfxId};
+use crate::ui::widgets::focus::{Focusable, WidgetInput, WidgetInputEvent};
+use crate::ui::widgets::theme::UiTheme;
+
+/// Size of buttons drawn with an image, matching the images of the menu.
+const IMAGE_BUTTON_SIZE: Vec2 = Vec2::new(256.0, 92.0);
+
+/// Button that sends a [`ButtonPressedEvent`] when clicked or confirmed.
+#[derive(Component, Debug)]
+pub struct MenuButton;
+
+/// Button leaving the screen it is on, it sounds different from the others.
+#[derive(Component, Debug)]
+pub struct BackButton;
+
+#[derive(Event, Debug)]
+pub struct ButtonPressedEvent {
+    pub entity: Entity,
+}
+
+enum ButtonContent {
+    Text(String),
+    Image(Handle<Image>),
+}
+
+/// Describes a button, spawned with [`ButtonWidget::spawn`].
+pub struct ButtonWidget {
+    content: ButtonContent,
+    size: Option<Vec2>,
+    text_color: Option<Color>,
+    back: bool,
+}
+
+impl ButtonWidget {
+    pub fn new(title: impl Into<String>) -> Self {
+        Self {
+            content: ButtonContent::Text(title.into()),
+            size: None,
+            text_color: None,
+            back: false,
+        }
+    }
+
+    /// Button drawn with an image instead of a label.
+    pub fn image(image: Handle<Image>) -> Self {
+        Self {
+            content: ButtonContent::Image(image),
+            size: Some(IMAGE_BUTTON_SIZE),
+            text_color: None,
+            back: false,
+        }
+    }
+
+    pub fn with_size(mut self, size: Vec2) -> Self {
+        self.size = Some(size);
+        self
+    }
+
+    /// Overrides the theme's text color, e.g. for buttons on bright menu art.
+    pub fn with_text_color(mut self, text_color: Color) -> Self {
+        self.text_color = Some(text_color);
+        self
+    }
+
+    /// Marks the button as a [`BackButton`].
+    pub fn back(mut self) -> Self {
+        self.back = true;
+        self
+    }
+
+    /// Spawns the button with `marker` to tell it apart from the others.
+    pub fn spawn(
+        self,
+        parent: &mut ChildBuilder,
+        theme: &UiTheme,
+        marker: impl Component,
+    ) -> Entity {
+        let mut button = ButtonBundle {
+            style: theme.widget_style(self.size.unwrap_or(theme.button_size)),
+            background_color: theme.normal_color.into(),
+            ..default()
+        };
+
+        let mut text = None;
+        match self.content {
+            ButtonContent::Text(title) => {
+                let mut text_style = theme.button_text_style();
+                if let Some(text_color) = self.text_color {
+                    text_style.color = text_color;
+                }
+                text = Some(TextBundle::from_section(title, text_style));
+            }
+            ButtonContent::Image(image) => {
+                button.image = UiImage::new(image);
+            }
+        }
+
+        let mut button_commands = parent.spawn((button, MenuButton, Focusable::default(), marker));
+        if let Some(text) = text {
+            button_commands.with_children(|parent| {
+                parent.spawn(text);
+            });
+        }
+        if self.back {
+            button_commands.insert(BackButton);
+        }
+
+        button_commands.id()
+    }
+}
+
+pub fn press_buttons(
+    mut input_reader: EventReader<WidgetInputEvent>,
+    button_query: Query<Has<BackButton>, With<MenuButton>>,
+    mut pressed_writer: EventWriter<ButtonPressedEvent>,
+    mut play_sfx_writer: EventWriter<PlaySfxEvent>,
+) {
+    for event in input_reader.read() {
+        if event.input != WidgetInput::Activate {
+            continue;
+        }
+        let Ok(is_back) = button_query.get(event.entity) else {
+            continue;
+        };
+
+        let sound = if is_back {
+            SfxId::UiBack
+        } else {
+            SfxId::UiClick
+        };
+        play_sfx_writer.send(PlaySfxEvent::new(sound));
+        pressed_writer.send(ButtonPressedEvent {
+            entity: event.entity,
+        });
+    }
+}
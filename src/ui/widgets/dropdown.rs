@@ -0,0 +1,252 @@
+use bevy::prelude::{
+    default, BuildChildren, ButtonBundle, Changed, ChildBuilder, Children, Commands, Component,
+    DespawnRecursiveExt, Entity, Event, EventReader, EventWriter, FlexDirection, NodeBundle,
+    PositionType, Query, Res, Style, Text, TextBundle, Val, Vec2, With, ZIndex,
+};
+
+use crate::audio::audio::{PlaySfxEvent, SfxId};
+use crate::ui::widgets::focus::{Focusable, WidgetInput, WidgetInputEvent};
+use crate::ui::widgets::theme::UiTheme;
+
+const DROPDOWN_SIZE: Vec2 = Vec2::new(300.0, 52.0);
+
+/// Choice between several options. Activating it opens the list of options below it,
+/// left and right step through them without opening it. Sends a [`DropdownChangedEvent`]
+/// when another option is picked.
+#[derive(Component, Debug)]
+pub struct Dropdown {
+    pub options: Vec<String>,
+    pub selected: usize,
+}
+
+/// Open list of options of the dropdown it points to.
+#[derive(Component, Debug)]
+pub struct DropdownList(pub Entity);
+
+/// Option `index` of the dropdown it points to, inside its open list.
+#[derive(Component, Debug)]
+pub struct DropdownOption {
+    pub dropdown: Entity,
+    pub index: usize,
+}
+
+#[derive(Event, Debug)]
+pub struct DropdownChangedEvent {
+    pub entity: Entity,
+    pub selected: usize,
+}
+
+/// Describes a dropdown, spawned with [`DropdownWidget::spawn`].
+pub struct DropdownWidget {
+    options: Vec<String>,
+    selected: usize,
+}
+
+impl DropdownWidget {
+    pub fn new(options: Vec<String>, selected: usize) -> Self {
+        Self { options, selected }
+    }
+
+    pub fn spawn(
+        self,
+        parent: &mut ChildBuilder,
+        theme: &UiTheme,
+        marker: impl Component,
+    ) -> Entity {
+        let label = self.options.get(self.selected).cloned().unwrap_or_default();
+
+        parent
+            .spawn((
+                ButtonBundle {
+                    style: theme.widget_style(DROPDOWN_SIZE),
+                    background_color: theme.normal_color.into(),
+                    ..default()
+                },
+                Dropdown {
+                    options: self.options,
+                    selected: self.selected,
+                },
+                Focusable::default(),
+                marker,
+            ))
+            .with_children(|parent| {
+                parent.spawn(TextBundle::from_section(label, theme.text_style()));
+            })
+            .id()
+    }
+}
+
+pub fn use_dropdowns(
+    mut commands: Commands,
+    theme: Res<UiTheme>,
+    mut input_reader: EventReader<WidgetInputEvent>,
+    mut dropdown_query: Query<&mut Dropdown>,
+    option_query: Query<&DropdownOption>,
+    list_query: Query<(Entity, &DropdownList)>,
+    mut changed_writer: EventWriter<DropdownChangedEvent>,
+) {
+    for event in input_reader.read() {
+        if let Ok(option) = option_query.get(event.entity) {
+            if event.input != WidgetInput::Activate {
+                continue;
+            }
+            close_dropdown_lists(&mut commands, &list_query);
+            if let Ok(mut dropdown) = dropdown_query.get_mut(option.dropdown) {
+                select_option(
+                    &mut dropdown,
+                    option.dropdown,
+                    option.index,
+                    &mut changed_writer,
+                );
+            }
+            continue;
+        }
+
+        let Ok(mut dropdown) = dropdown_query.get_mut(event.entity) else {
+            continue;
+        };
+        let count = dropdown.options.len().max(1) as isize;
+        let step = match event.input {
+            WidgetInput::Increase => 1,
+            WidgetInput::Decrease => -1,
+            WidgetInput::Activate => {
+                let was_open = list_query.iter().any(|(_, list)| list.0 == event.entity);
+                close_dropdown_lists(&mut commands, &list_query);
+                if !was_open {
+                    open_dropdown_list(&mut commands, &theme, event.entity, &dropdown);
+                }
+                continue;
+            }
+        };
+
+        let index = (dropdown.selected as isize + step).rem_euclid(count) as usize;
+        select_option(&mut dropdown, event.entity, index, &mut changed_writer);
+    }
+}
+
+/// Opening, closing and picking from the list click, stepping through the options doesn't.
+pub fn click_dropdowns(
+    mut input_reader: EventReader<WidgetInputEvent>,
+    dropdown_query: Query<(), With<Dropdown>>,
+    option_query: Query<(), With<DropdownOption>>,
+    mut play_sfx_writer: EventWriter<PlaySfxEvent>,
+) {
+    for event in input_reader.read() {
+        let is_dropdown =
+            dropdown_query.contains(event.entity) || option_query.contains(event.entity);
+        if event.input == WidgetInput::Activate && is_dropdown {
+            play_sfx_writer.send(PlaySfxEvent::new(SfxId::UiClick));
+        }
+    }
+}
+
+pub fn update_dropdown_texts(
+    dropdown_query: Query<(&Dropdown, &Children), Changed<Dropdown>>,
+    mut text_query: Query<&mut Text>,
+) {
+    for (dropdown, children) in dropdown_query.iter() {
+        let label = dropdown
+            .options
+            .get(dropdown.selected)
+            .cloned()
+            .unwrap_or_default();
+
+        for child in children.iter() {
+            if let Ok(mut text) = text_query.get_mut(*child) {
+                text.sections[0].value = label.clone();
+            }
+        }
+    }
+}
+
+fn select_option(
+    dropdown: &mut Dropdown,
+    entity: Entity,
+    index: usize,
+    changed_writer: &mut EventWriter<DropdownChangedEvent>,
+) {
+    if index == dropdown.selected || index >= dropdown.options.len() {
+        return;
+    }
+
+    dropdown.selected = index;
+    changed_writer.send(DropdownChangedEvent {
+        entity,
+        selected: index,
+    });
+}
+
+fn open_dropdown_list(
+    commands: &mut Commands,
+    theme: &UiTheme,
+    entity: Entity,
+    dropdown: &Dropdown,
+) {
+    let list = commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    top: Val::Percent(100.0),
+                    left: Val::Px(0.0),
+                    flex_direction: FlexDirection::Column,
+                    ..default()
+                },
+                background_color: theme.panel_color.into(),
+                // Drawn over the widgets below the dropdown.
+                z_index: ZIndex::Global(20),
+                ..default()
+            },
+            DropdownList(entity),
+        ))
+        .with_children(|parent| {
+            for (index, option) in dropdown.options.iter().enumerate() {
+                parent
+                    .spawn((
+                        ButtonBundle {
+                            style: theme.widget_style(DROPDOWN_SIZE),
+                            background_color: theme.normal_color.into(),
+                            ..default()
+                        },
+                        Focusable {
+                            focused: false,
+                            selected: index == dropdown.selected,
+                        },
+                        DropdownOption {
+                            dropdown: entity,
+                            index,
+                        },
+                    ))
+                    .with_children(|parent| {
+                        parent.spawn(TextBundle::from_section(option.clone(), theme.text_style()));
+                    });
+            }
+        })
+        .id();
+
+    commands.entity(entity).add_child(list);
+}
+
+fn close_dropdown_lists(commands: &mut Commands, list_query: &Query<(Entity, &DropdownList)>) {
+    for (entity, _) in list_query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+/// Closes open lists whose dropdown lost the focus to a widget outside of them.
+pub fn close_unfocused_dropdowns(
+    mut commands: Commands,
+    list_query: Query<(Entity, &DropdownList)>,
+    focus_query: Query<(Entity, &Focusable), Changed<Focusable>>,
+    option_query: Query<(), With<DropdownOption>>,
+) {
+    let focus_moved_away = focus_query.iter().any(|(entity, focusable)| {
+        focusable.focused
+            && !option_query.contains(entity)
+            && list_query.iter().all(|(_, list)| list.0 != entity)
+    });
+
+    if focus_moved_away {
+        close_dropdown_lists(&mut commands, &list_query);
+    }
+}
@@ -0,0 +1,212 @@
+use bevy::hierarchy::HierarchyQueryExt;
+use bevy::prelude::{
+    BackgroundColor, ButtonInput, Changed, Component, DetectChanges, Entity, Event, EventWriter,
+    GamepadButton, GamepadButtonType, GlobalTransform, Interaction, KeyCode, Mut, Parent, Query,
+    Ref, Res, ViewVisibility,
+};
+
+use crate::ui::widgets::theme::UiTheme;
+
+/// Widget that can be reached with the keyboard or a gamepad.
+#[derive(Component, Debug, Default)]
+pub struct Focusable {
+    /// Whether the keyboard and gamepad act on this widget, true for one at most.
+    pub focused: bool,
+    /// Whether the widget stands for the current choice, e.g. the open tab.
+    pub selected: bool,
+}
+
+/// Root of a menu. Only the widgets of the layer with the highest priority are navigated,
+/// so a menu opened over another one takes the focus over.
+#[derive(Component, Debug)]
+pub struct FocusLayer {
+    pub priority: i32,
+}
+
+impl FocusLayer {
+    pub fn new(priority: i32) -> Self {
+        Self { priority }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WidgetInput {
+    /// Clicked, or confirmed with Enter or the gamepad's south button.
+    Activate,
+    /// Right on the keyboard or the d-pad.
+    Increase,
+    /// Left on the keyboard or the d-pad.
+    Decrease,
+}
+
+/// Input aimed at one widget, each widget type turns it into its own change event.
+#[derive(Event, Debug)]
+pub struct WidgetInputEvent {
+    pub entity: Entity,
+    pub input: WidgetInput,
+}
+
+/// The mouse and the keyboard share one focus, so they never highlight two widgets.
+pub fn focus_hovered_widget(
+    hovered_query: Query<(Entity, &Interaction), Changed<Interaction>>,
+    mut focusable_query: Query<(Entity, &mut Focusable)>,
+) {
+    for (hovered_entity, interaction) in hovered_query.iter() {
+        if *interaction == Interaction::None || !focusable_query.contains(hovered_entity) {
+            continue;
+        }
+
+        for (entity, focusable) in focusable_query.iter_mut() {
+            set_focused(focusable, entity == hovered_entity);
+        }
+    }
+}
+
+/// Up and down move the focus through the visible widgets of the top layer from top to
+/// bottom, wrapping around at the ends. The first press focuses the top widget.
+pub fn navigate_focus(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    gamepad_buttons: Res<ButtonInput<GamepadButton>>,
+    mut focusable_query: Query<(Entity, &mut Focusable, &GlobalTransform, &ViewVisibility)>,
+    layer_query: Query<(Entity, &FocusLayer)>,
+    parent_query: Query<&Parent>,
+) {
+    let step: isize = if keyboard_input.just_pressed(KeyCode::ArrowUp)
+        || gamepad_just_pressed(&gamepad_buttons, GamepadButtonType::DPadUp)
+    {
+        -1
+    } else if keyboard_input.just_pressed(KeyCode::ArrowDown)
+        || gamepad_just_pressed(&gamepad_buttons, GamepadButtonType::DPadDown)
+    {
+        1
+    } else {
+        return;
+    };
+    let Some(layer) = top_layer(&layer_query) else {
+        return;
+    };
+
+    let mut widgets: Vec<_> = focusable_query
+        .iter_mut()
+        .filter(|(entity, _, _, visibility)| {
+            visibility.get() && in_layer(*entity, layer, &parent_query)
+        })
+        .collect();
+    if widgets.is_empty() {
+        return;
+    }
+    // Interface nodes are laid out with y growing downwards, rows keep their x order.
+    widgets.sort_by(|(_, _, a, _), (_, _, b, _)| {
+        let (a, b) = (a.translation(), b.translation());
+        a.y.total_cmp(&b.y).then(a.x.total_cmp(&b.x))
+    });
+
+    let next = match widgets
+        .iter()
+        .position(|(_, focusable, _, _)| focusable.focused)
+    {
+        Some(index) => (index as isize + step).rem_euclid(widgets.len() as isize) as usize,
+        None => 0,
+    };
+    for (index, (_, focusable, _, _)) in widgets.into_iter().enumerate() {
+        set_focused(focusable, index == next);
+    }
+}
+
+/// Confirm, left and right go to the focused widget of the top layer.
+pub fn send_focused_input(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    gamepad_buttons: Res<ButtonInput<GamepadButton>>,
+    focusable_query: Query<(Entity, &Focusable)>,
+    layer_query: Query<(Entity, &FocusLayer)>,
+    parent_query: Query<&Parent>,
+    mut input_writer: EventWriter<WidgetInputEvent>,
+) {
+    let pressed = |key: KeyCode, button_type: GamepadButtonType| {
+        keyboard_input.just_pressed(key) || gamepad_just_pressed(&gamepad_buttons, button_type)
+    };
+    let input = if pressed(KeyCode::Enter, GamepadButtonType::South) {
+        WidgetInput::Activate
+    } else if pressed(KeyCode::ArrowRight, GamepadButtonType::DPadRight) {
+        WidgetInput::Increase
+    } else if pressed(KeyCode::ArrowLeft, GamepadButtonType::DPadLeft) {
+        WidgetInput::Decrease
+    } else {
+        return;
+    };
+    let Some(layer) = top_layer(&layer_query) else {
+        return;
+    };
+
+    let focused = focusable_query
+        .iter()
+        .find(|(entity, focusable)| focusable.focused && in_layer(*entity, layer, &parent_query));
+    if let Some((entity, _)) = focused {
+        input_writer.send(WidgetInputEvent { entity, input });
+    }
+}
+
+pub fn activate_clicked_widgets(
+    clicked_query: Query<(Entity, &Interaction, &Focusable), Changed<Interaction>>,
+    mut input_writer: EventWriter<WidgetInputEvent>,
+) {
+    for (entity, interaction, _) in clicked_query.iter() {
+        if *interaction == Interaction::Pressed {
+            input_writer.send(WidgetInputEvent {
+                entity,
+                input: WidgetInput::Activate,
+            });
+        }
+    }
+}
+
+/// Recolors widgets whose interaction, focus or selection changed this frame.
+pub fn update_widget_colors(
+    theme: Res<UiTheme>,
+    mut widget_query: Query<(Ref<Interaction>, Ref<Focusable>, &mut BackgroundColor)>,
+) {
+    for (interaction, focusable, mut background_color) in widget_query.iter_mut() {
+        if !interaction.is_changed() && !focusable.is_changed() && !theme.is_changed() {
+            continue;
+        }
+
+        let color = match *interaction {
+            Interaction::Pressed => theme.pressed_color,
+            Interaction::Hovered => theme.hovered_color,
+            Interaction::None if focusable.focused => theme.hovered_color,
+            Interaction::None if focusable.selected => theme.selected_color,
+            Interaction::None => theme.normal_color,
+        };
+        *background_color = color.into();
+    }
+}
+
+/// Only touches widgets whose focus actually changes, so the others keep their color.
+fn set_focused(mut focusable: Mut<Focusable>, focused: bool) {
+    if focusable.focused != focused {
+        focusable.focused = focused;
+    }
+}
+
+fn gamepad_just_pressed(
+    gamepad_buttons: &ButtonInput<GamepadButton>,
+    button_type: GamepadButtonType,
+) -> bool {
+    gamepad_buttons
+        .get_just_pressed()
+        .any(|button| button.button_type == button_type)
+}
+
+fn top_layer(layer_query: &Query<(Entity, &FocusLayer)>) -> Option<Entity> {
+    layer_query
+        .iter()
+        .max_by_key(|(_, layer)| layer.priority)
+        .map(|(entity, _)| entity)
+}
+
+fn in_layer(entity: Entity, layer: Entity, parent_query: &Query<&Parent>) -> bool {
+    entity == layer
+        || parent_query
+            .iter_ancestors(entity)
+            .any(|ancestor| ancestor == layer)
+}
@@ -0,0 +1,119 @@
+use bevy::prelude::{
+    default, BuildChildren, ButtonBundle, ChildBuilder, Children, Component, Entity, EventReader,
+    EventWriter, Query, Res, Text, TextBundle, Vec2,
+};
+
+use crate::audio::audio::{PlaySfxEvent, SfxId};
+use crate::game::controls::controls::{
+    key_label, ControlBindings, ControlledAction, PendingRebind, RebindRequest,
+};
+use crate::ui::widgets::focus::{Focusable, WidgetInput, WidgetInputEvent};
+use crate::ui::widgets::theme::UiTheme;
+
+const KEY_BINDING_SIZE: Vec2 = Vec2::new(260.0, 52.0);
+
+/// Shows one of the keys bound to an action, activating it waits for a key to replace
+/// it with. The outcome arrives as a [`RebindEvent`](crate::game::controls::controls::RebindEvent).
+#[derive(Component, Debug)]
+pub struct KeyBinding {
+    pub action: ControlledAction,
+    pub slot: usize,
+}
+
+/// Describes a key binding, spawned with [`KeyBindingWidget::spawn`].
+pub struct KeyBindingWidget {
+    action: ControlledAction,
+    slot: usize,
+}
+
+impl KeyBindingWidget {
+    pub fn new(action: ControlledAction, slot: usize) -> Self {
+        Self { action, slot }
+    }
+
+    pub fn spawn(
+        self,
+        parent: &mut ChildBuilder,
+        theme: &UiTheme,
+        bindings: &ControlBindings,
+        pending_rebind: &PendingRebind,
+    ) -> Entity {
+        parent
+            .spawn((
+                ButtonBundle {
+                    style: theme.widget_style(KEY_BINDING_SIZE),
+                    background_color: theme.normal_color.into(),
+                    ..default()
+                },
+                KeyBinding {
+                    action: self.action,
+                    slot: self.slot,
+                },
+                Focusable::default(),
+            ))
+            .with_children(|parent| {
+                parent.spawn(TextBundle::from_section(
+                    binding_label(bindings, pending_rebind, self.action, self.slot),
+                    theme.text_style(),
+                ));
+            })
+            .id()
+    }
+}
+
+pub fn request_rebinds(
+    mut input_reader: EventReader<WidgetInputEvent>,
+    key_binding_query: Query<&KeyBinding>,
+    mut rebind_request_writer: EventWriter<RebindRequest>,
+    mut play_sfx_writer: EventWriter<PlaySfxEvent>,
+) {
+    for event in input_reader.read() {
+        if event.input != WidgetInput::Activate {
+            continue;
+        }
+
+        if let Ok(key_binding) = key_binding_query.get(event.entity) {
+            play_sfx_writer.send(PlaySfxEvent::new(SfxId::UiClick));
+            rebind_request_writer.send(RebindRequest {
+                action: key_binding.action,
+                slot: key_binding.slot,
+            });
+        }
+    }
+}
+
+pub fn update_key_binding_texts(
+    bindings: Res<ControlBindings>,
+    pending_rebind: Res<PendingRebind>,
+    key_binding_query: Query<(&KeyBinding, &Children)>,
+    mut text_query: Query<&mut Text>,
+) {
+    for (key_binding, children) in key_binding_query.iter() {
+        for child in children.iter() {
+            if let Ok(mut text) = text_query.get_mut(*child) {
+                text.sections[0].value = binding_label(
+                    &bindings,
+                    &pending_rebind,
+                    key_binding.action,
+                    key_binding.slot,
+                );
+            }
+        }
+    }
+}
+
+fn binding_label(
+    bindings: &ControlBindings,
+    pending_rebind: &PendingRebind,
+    action: ControlledAction,
+    slot: usize,
+) -> String {
+    if pending_rebind.action == Some(action) && pending_rebind.slot == slot {
+        return "Press a key...".to_string();
+    }
+
+    bindings
+        .key_in_slot(action, slot)
+        .map(|key| key_label(&key))
+        .unwrap_or_else(|| "Unbound".to_string())
+}
@@ -0,0 +1,76 @@
+use bevy::prelude::{not, resource_changed, App, Condition, IntoSystemConfigs, Plugin, Update};
+
+use crate::game::controls::controls::{
+    capture_rebind, rebind_in_progress, ControlBindings, PendingRebind,
+};
+use button::{press_buttons, ButtonPressedEvent};
+use dropdown::{
+    click_dropdowns, close_unfocused_dropdowns, update_dropdown_texts, use_dropdowns,
+    DropdownChangedEvent,
+};
+use focus::{
+    activate_clicked_widgets, focus_hovered_widget, navigate_focus, send_focused_input,
+    update_widget_colors, WidgetInputEvent,
+};
+use key_binding::{request_rebinds, update_key_binding_texts};
+use slider::{drag_sliders, step_sliders, update_slider_fills, SliderChangedEvent};
+use theme::UiTheme;
+use toggle::{flip_toggles, update_toggle_texts, ToggleChangedEvent};
+
+pub mod button;
+pub mod dropdown;
+pub mod focus;
+pub mod key_binding;
+pub mod slider;
+pub mod theme;
+pub mod toggle;
+
+/// Building blocks the menus are made of. Each widget turns clicks and the focused
+/// input into its own change event.
+pub struct WidgetsPlugin;
+
+impl Plugin for WidgetsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<UiTheme>()
+            .add_event::<WidgetInputEvent>()
+            .add_event::<ButtonPressedEvent>()
+            .add_event::<SliderChangedEvent>()
+            .add_event::<ToggleChangedEvent>()
+            .add_event::<DropdownChangedEvent>()
+            .add_systems(
+                Update,
+                (
+                    (
+                        focus_hovered_widget,
+                        close_unfocused_dropdowns,
+                        // Keys pressed while rebinding belong to the rebind.
+                        (navigate_focus, send_focused_input).run_if(not(rebind_in_progress)),
+                        activate_clicked_widgets,
+                    )
+                        .chain(),
+                    (
+                        press_buttons,
+                        flip_toggles,
+                        step_sliders,
+                        drag_sliders,
+                        use_dropdowns,
+                        click_dropdowns,
+                        request_rebinds,
+                    ),
+                    (
+                        update_widget_colors,
+                        update_toggle_texts,
+                        update_slider_fills,
+                        update_dropdown_texts,
+                        update_key_binding_texts.run_if(
+                            resource_changed::<ControlBindings>
+                                .or_else(resource_changed::<PendingRebind>),
+                        ),
+                    ),
+                )
+                    .chain()
+                    // A key confirming a key binding must not be taken as the new binding.
+                    .after(capture_rebind),
+            );
+    }
+}
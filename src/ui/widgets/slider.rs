@@ -0,0 +1,185 @@
+use bevy::prelude::{
+    default, BuildChildren, ButtonBundle, Changed, ChildBuilder, Component, Entity, Event,
+    EventReader, EventWriter, Interaction, NodeBundle, Query, Style, UiRect, Val, Vec2,
+};
+use bevy::ui::RelativeCursorPosition;
+
+use crate::ui::widgets::focus::{Focusable, WidgetInput, WidgetInputEvent};
+use crate::ui::widgets::theme::UiTheme;
+
+const SLIDER_SIZE: Vec2 = Vec2::new(300.0, 52.0);
+const TRACK_HEIGHT: f32 = 12.0;
+
+/// Value between 0 and 1 picked by dragging or with left and right, sends a
+/// [`SliderChangedEvent`] when it changes.
+#[derive(Component, Debug)]
+pub struct Slider {
+    pub value: f32,
+    /// The value snaps to multiples of this.
+    pub step: f32,
+}
+
+impl Slider {
+    /// Snaps and clamps `value`, returns whether the slider changed.
+    fn set(&mut self, value: f32) -> bool {
+        let value = ((value / self.step).round() * self.step).clamp(0.0, 1.0);
+        if (value - self.value).abs() < f32::EPSILON {
+            return false;
+        }
+
+        self.value = value;
+        true
+    }
+}
+
+/// Track of the slider it points to, the cursor position on it picks the value.
+#[derive(Component, Debug)]
+pub struct SliderTrack(pub Entity);
+
+/// Filled part of the track of the slider it points to.
+#[derive(Component, Debug)]
+pub struct SliderFill(pub Entity);
+
+#[derive(Event, Debug)]
+pub struct SliderChangedEvent {
+    pub entity: Entity,
+    pub value: f32,
+}
+
+/// Describes a slider, spawned with [`SliderWidget::spawn`].
+pub struct SliderWidget {
+    value: f32,
+    step: f32,
+}
+
+impl SliderWidget {
+    pub fn new(value: f32) -> Self {
+        Self { value, step: 0.05 }
+    }
+
+    pub fn with_step(mut self, step: f32) -> Self {
+        self.step = step;
+        self
+    }
+
+    pub fn spawn(
+        self,
+        parent: &mut ChildBuilder,
+        theme: &UiTheme,
+        marker: impl Component,
+    ) -> Entity {
+        let mut slider = parent.spawn((
+            ButtonBundle {
+                style: Style {
+                    padding: UiRect::horizontal(Val::Px(12.0)),
+                    ..theme.widget_style(SLIDER_SIZE)
+                },
+                background_color: theme.normal_color.into(),
+                ..default()
+            },
+            Slider {
+                value: self.value,
+                step: self.step,
+            },
+            Focusable::default(),
+            marker,
+        ));
+        let entity = slider.id();
+
+        slider.with_children(|parent| {
+            parent
+                .spawn((
+                    NodeBundle {
+                        style: Style {
+                            width: Val::Percent(100.0),
+                            height: Val::Px(TRACK_HEIGHT),
+                            ..default()
+                        },
+                        background_color: theme.track_color.into(),
+                        ..default()
+                    },
+                    SliderTrack(entity),
+                    RelativeCursorPosition::default(),
+                ))
+                .with_children(|parent| {
+                    parent.spawn((
+                        NodeBundle {
+                            style: Style {
+                                width: Val::Percent(self.value * 100.0),
+                                height: Val::Percent(100.0),
+                                ..default()
+                            },
+                            background_color: theme.fill_color.into(),
+                            ..default()
+                        },
+                        SliderFill(entity),
+                    ));
+                });
+        });
+
+        entity
+    }
+}
+
+/// Left and right step the value of the focused slider.
+pub fn step_sliders(
+    mut input_reader: EventReader<WidgetInputEvent>,
+    mut slider_query: Query<&mut Slider>,
+    mut changed_writer: EventWriter<SliderChangedEvent>,
+) {
+    for event in input_reader.read() {
+        let Ok(mut slider) = slider_query.get_mut(event.entity) else {
+            continue;
+        };
+        let step = match event.input {
+            WidgetInput::Increase => slider.step,
+            WidgetInput::Decrease => -slider.step,
+            WidgetInput::Activate => continue,
+        };
+
+        let value = slider.value + step;
+        if slider.set(value) {
+            changed_writer.send(SliderChangedEvent {
+                entity: event.entity,
+                value: slider.value,
+            });
+        }
+    }
+}
+
+/// While a slider is held down, the value follows the cursor along its track.
+pub fn drag_sliders(
+    track_query: Query<(&SliderTrack, &RelativeCursorPosition)>,
+    mut slider_query: Query<(&mut Slider, &Interaction)>,
+    mut changed_writer: EventWriter<SliderChangedEvent>,
+) {
+    for (track, cursor_position) in track_query.iter() {
+        let Ok((mut slider, interaction)) = slider_query.get_mut(track.0) else {
+            continue;
+        };
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        let Some(cursor) = cursor_position.normalized else {
+            continue;
+        };
+
+        if slider.set(cursor.x) {
+            changed_writer.send(SliderChangedEvent {
+                entity: track.0,
+                value: slider.value,
+            });
+        }
+    }
+}
+
+pub fn update_slider_fills(
+    slider_query: Query<&Slider, Changed<Slider>>,
+    mut fill_query: Query<(&SliderFill, &mut Style)>,
+) {
+    for (fill, mut style) in fill_query.iter_mut() {
+        if let Ok(slider) = slider_query.get(fill.0) {
+            style.width = Val::Percent(slider.value * 100.0);
+        }
+    }
+}
@@ -0,0 +1,93 @@
+use bevy::prelude::{
+    AlignItems, AssetServer, Color, FromWorld, Handle, JustifyContent, Resource, Style, TextStyle,
+    Val, Vec2, World,
+};
+use bevy::text::Font;
+
+pub const NORMAL_BUTTON_COLOR: Color = Color::NONE;
+pub const HOVERED_BUTTON_COLOR: Color = Color::srgb(0.25, 0.25, 0.25);
+pub const PRESSED_BUTTON_COLOR: Color = Color::srgb(0.35, 0.75, 0.35);
+pub const SELECTED_BUTTON_COLOR: Color = Color::srgb(0.35, 0.35, 0.35);
+
+/// Fonts, colors and sizes every widget is drawn with.
+#[derive(Resource, Debug, Clone)]
+pub struct UiTheme {
+    pub title_font: Handle<Font>,
+    pub text_font: Handle<Font>,
+    pub text_color: Color,
+    pub title_font_size: f32,
+    pub button_font_size: f32,
+    pub text_font_size: f32,
+    /// Background of widgets nobody points at. Buttons are drawn straight on the menu
+    /// art, only hover and focus give them a backdrop.
+    pub normal_color: Color,
+    pub hovered_color: Color,
+    pub pressed_color: Color,
+    /// Background of the widget standing for the current choice, e.g. the open tab.
+    pub selected_color: Color,
+    pub track_color: Color,
+    pub fill_color: Color,
+    /// Background of pop ups such as an open dropdown.
+    pub panel_color: Color,
+    pub button_size: Vec2,
+}
+
+impl FromWorld for UiTheme {
+    fn from_world(world: &mut World) -> Self {
+        let asset_server = world.resource::<AssetServer>();
+
+        Self {
+            title_font: asset_server.load("fonts/Paint-Peel-Cyr.ttf"),
+            text_font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+            text_color: Color::WHITE,
+            title_font_size: 64.0,
+            button_font_size: 50.0,
+            text_font_size: 32.0,
+            normal_color: NORMAL_BUTTON_COLOR,
+            hovered_color: HOVERED_BUTTON_COLOR,
+            pressed_color: PRESSED_BUTTON_COLOR,
+            selected_color: SELECTED_BUTTON_COLOR,
+            track_color: Color::srgb(0.2, 0.2, 0.2),
+            fill_color: Color::srgb(0.85, 0.55, 0.2),
+            panel_color: Color::srgba(0.05, 0.05, 0.05, 0.95),
+            button_size: Vec2::new(300.0, 80.0),
+        }
+    }
+}
+
+impl UiTheme {
+    pub fn title_text_style(&self) -> TextStyle {
+        TextStyle {
+            font: self.title_font.clone(),
+            font_size: self.title_font_size,
+            color: self.text_color,
+        }
+    }
+
+    pub fn button_text_style(&self) -> TextStyle {
+        TextStyle {
+            font: self.title_font.clone(),
+            font_size: self.button_font_size,
+            color: self.text_color,
+        }
+    }
+
+    pub fn text_style(&self) -> TextStyle {
+        TextStyle {
+            font: self.text_font.clone(),
+            font_size: self.text_font_size,
+            color: self.text_color,
+        }
+    }
+
+    /// Centers the content of a widget of the given size.
+    pub fn widget_style(&self, size: Vec2) -> Style {
+        Style {
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            width: Val::Px(size.x),
+            height: Val::Px(size.y),
+            ..Style::DEFAULT
+        }
+    }
+}
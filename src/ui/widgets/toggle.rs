@@ -0,0 +1,97 @@
+use bevy::prelude::{
+    default, BuildChildren, ButtonBundle, Changed, ChildBuilder, Children, Component, Entity,
+    Event, EventReader, EventWriter, Query, Text, TextBundle, Vec2,
+};
+
+use crate::audio::audio::{PlaySfxEvent, SfxId};
+use crate::ui::widgets::focus::{Focusable, WidgetInputEvent};
+use crate::ui::widgets::theme::UiTheme;
+
+const TOGGLE_SIZE: Vec2 = Vec2::new(200.0, 52.0);
+
+/// Switch between on and off, sends a [`ToggleChangedEvent`] when flipped.
+#[derive(Component, Debug)]
+pub struct Toggle {
+    pub value: bool,
+}
+
+#[derive(Event, Debug)]
+pub struct ToggleChangedEvent {
+    pub entity: Entity,
+    pub value: bool,
+}
+
+/// Describes a toggle, spawned with [`ToggleWidget::spawn`].
+pub struct ToggleWidget {
+    value: bool,
+}
+
+impl ToggleWidget {
+    pub fn new(value: bool) -> Self {
+        Self { value }
+    }
+
+    pub fn spawn(
+        self,
+        parent: &mut ChildBuilder,
+        theme: &UiTheme,
+        marker: impl Component,
+    ) -> Entity {
+        parent
+            .spawn((
+                ButtonBundle {
+                    style: theme.widget_style(TOGGLE_SIZE),
+                    background_color: theme.normal_color.into(),
+                    ..default()
+                },
+                Toggle { value: self.value },
+                Focusable::default(),
+                marker,
+            ))
+            .with_children(|parent| {
+                parent.spawn(TextBundle::from_section(
+                    toggle_label(self.value),
+                    theme.text_style(),
+                ));
+            })
+            .id()
+    }
+}
+
+/// Any input flips the toggle, left and right included.
+pub fn flip_toggles(
+    mut input_reader: EventReader<WidgetInputEvent>,
+    mut toggle_query: Query<&mut Toggle>,
+    mut changed_writer: EventWriter<ToggleChangedEvent>,
+    mut play_sfx_writer: EventWriter<PlaySfxEvent>,
+) {
+    for event in input_reader.read() {
+        let Ok(mut toggle) = toggle_query.get_mut(event.entity) else {
+            continue;
+        };
+
+        play_sfx_writer.send(PlaySfxEvent::new(SfxId::UiClick));
+        toggle.value = !toggle.value;
+        changed_writer.send(ToggleChangedEvent {
+            entity: event.entity,
+            value: toggle.value,
+        });
+    }
+}
+
+pub fn update_toggle_texts(
+    toggle_query: Query<(&Toggle, &Children), Changed<Toggle>>,
+    mut text_query: Query<&mut Text>,
+) {
+    for (toggle, children) in toggle_query.iter() {
+        for child in children.iter() {
+            if let Ok(mut text) = text_query.get_mut(*child) {
+                text.sections[0].value = toggle_label(toggle.value);
+            }
+        }
+    }
+}
+
+fn toggle_label(value: bool) -> String {
+    if value { "On" } else { "Off" }.to_string()
+}
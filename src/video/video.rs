@@ -0,0 +1,220 @@
+use std::io;
+
+use bevy::prelude::{
+    default, on_event, resource_changed, App, Camera, Condition, IntoSystemConfigs,
+    OrthographicProjection, Plugin, Query, Res, ResMut, Resource, UVec2, Update, Window,
+    WindowPlugin, With,
+};
+use bevy::render::camera::{ScalingMode, Viewport};
+use bevy::ui::UiScale;
+use bevy::window::{PresentMode, PrimaryWindow, WindowMode, WindowResized, WindowResolution};
+use serde::{Deserialize, Serialize};
+
+use crate::game::camera::camera::GameCamera;
+use crate::settings::settings::{load_settings, save_settings};
+
+/// Size of the world area the camera shows at zoom 1, whatever the window size. The
+/// interface is laid out for this size as well and scaled along with the view.
+pub const INTERNAL_RESOLUTION: UVec2 = UVec2::new(1920, 1080);
+
+/// Window sizes offered in the settings.
+pub const RESOLUTIONS: [UVec2; 5] = [
+    UVec2::new(1280, 720),
+    UVec2::new(1600, 900),
+    UVec2::new(1920, 1080),
+    UVec2::new(2560, 1440),
+    UVec2::new(3840, 2160),
+];
+
+pub struct VideoPlugin;
+
+impl Plugin for VideoPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<VideoSettings>().add_systems(
+            Update,
+            (
+                apply_window_settings.run_if(resource_changed::<VideoSettings>),
+                apply_render_scaling
+                    .run_if(resource_changed::<VideoSettings>.or_else(on_event::<WindowResized>())),
+            )
+                .chain(),
+        );
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum WindowModeSetting {
+    Windowed,
+    Borderless,
+    #[default]
+    Fullscreen,
+}
+
+impl WindowModeSetting {
+    pub const MODES: [Self; 3] = [
+        WindowModeSetting::Windowed,
+        WindowModeSetting::Borderless,
+        WindowModeSetting::Fullscreen,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            WindowModeSetting::Windowed => "Windowed",
+            WindowModeSetting::Borderless => "Borderless",
+            WindowModeSetting::Fullscreen => "Fullscreen",
+        }
+    }
+
+    /// Fullscreen switches the monitor to the video mode closest to the chosen resolution,
+    /// borderless always covers the monitor at its current one.
+    pub fn window_mode(&self) -> WindowMode {
+        match self {
+            WindowModeSetting::Windowed => WindowMode::Windowed,
+            WindowModeSetting::Borderless => WindowMode::BorderlessFullscreen,
+            WindowModeSetting::Fullscreen => WindowMode::SizedFullscreen,
+        }
+    }
+}
+
+/// Option of [`VideoSettings`] that can be changed from the settings menu.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VideoOption {
+    WindowMode,
+    Resolution,
+    Vsync,
+    PixelPerfect,
+}
+
+impl VideoOption {
+    pub const OPTIONS: [Self; 4] = [
+        VideoOption::WindowMode,
+        VideoOption::Resolution,
+        VideoOption::Vsync,
+        VideoOption::PixelPerfect,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            VideoOption::WindowMode => "Window",
+            VideoOption::Resolution => "Resolution",
+            VideoOption::Vsync => "VSync",
+            VideoOption::PixelPerfect => "Pixel Perfect",
+        }
+    }
+}
+
+/// Window and scaling options, persisted to `video.ron` in the user config directory.
+#[derive(Resource, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VideoSettings {
+    pub window_mode: WindowModeSetting,
+    pub resolution: UVec2,
+    pub vsync: bool,
+    /// Scales the internal resolution by whole numbers only, with black bars around it,
+    /// so every texel covers the same amount of screen pixels.
+    pub pixel_perfect: bool,
+}
+
+impl Default for VideoSettings {
+    fn default() -> Self {
+        Self {
+            window_mode: WindowModeSetting::Fullscreen,
+            resolution: INTERNAL_RESOLUTION,
+            vsync: true,
+            pixel_perfect: false,
+        }
+    }
+}
+
+impl VideoSettings {
+    const FILE_NAME: &'static str = "video.ron";
+
+    pub fn load() -> Self {
+        load_settings(Self::FILE_NAME)
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        save_settings(Self::FILE_NAME, self)
+    }
+
+    pub fn present_mode(&self) -> PresentMode {
+        if self.vsync {
+            PresentMode::AutoVsync
+        } else {
+            PresentMode::AutoNoVsync
+        }
+    }
+
+    /// Window the game opens with, so it starts in the saved mode right away.
+    pub fn window_plugin(&self) -> WindowPlugin {
+        WindowPlugin {
+            primary_window: Some(Window {
+                resolution: WindowResolution::new(
+                    self.resolution.x as f32,
+                    self.resolution.y as f32,
+                ),
+                title: "Last of Pipisenok".to_string(),
+                mode: self.window_mode.window_mode(),
+                present_mode: self.present_mode(),
+                ..default()
+            }),
+            ..default()
+        }
+    }
+}
+
+pub fn apply_window_settings(
+    video_settings: Res<VideoSettings>,
+    mut window_query: Query<&mut Window, With<PrimaryWindow>>,
+) {
+    let Ok(mut window) = window_query.get_single_mut() else {
+        return;
+    };
+
+    window.mode = video_settings.window_mode.window_mode();
+    window.present_mode = video_settings.present_mode();
+    window.resolution.set(
+        video_settings.resolution.x as f32,
+        video_settings.resolution.y as f32,
+    );
+}
+
+/// Fits the internal resolution into the window. Pixel perfect scaling falls back to
+/// the smooth one when the window is smaller than the internal resolution.
+pub fn apply_render_scaling(
+    video_settings: Res<VideoSettings>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    mut camera_query: Query<(&mut Camera, &mut OrthographicProjection), With<GameCamera>>,
+    mut ui_scale: ResMut<UiScale>,
+) {
+    let Ok(window) = window_query.get_single() else {
+        return;
+    };
+    let Ok((mut camera, mut projection)) = camera_query.get_single_mut() else {
+        return;
+    };
+
+    let window_size = UVec2::new(window.physical_width(), window.physical_height());
+    let factor = (window_size / INTERNAL_RESOLUTION).min_element();
+    let internal_size = INTERNAL_RESOLUTION.as_vec2();
+
+    if video_settings.pixel_perfect && factor >= 1 {
+        let view_size = INTERNAL_RESOLUTION * factor;
+        camera.viewport = Some(Viewport {
+            physical_position: (window_size - view_size) / 2,
+            physical_size: view_size,
+            ..default()
+        });
+        projection.scaling_mode = ScalingMode::Fixed {
+            width: internal_size.x,
+            height: internal_size.y,
+        };
+        ui_scale.0 = factor as f32 / window.scale_factor();
+    } else {
+        camera.viewport = None;
+        projection.scaling_mode = ScalingMode::AutoMin {
+            min_width: internal_size.x,
+            min_height: internal_size.y,
+        };
+        ui_scale.0 = (window.width() / internal_size.x).min(window.height() / internal_size.y);
+    }
+}
@@ -0,0 +1,52 @@
+use bevy::prelude::{
+    App, Component, IntoSystemConfigs, Plugin, PostUpdate, Query, Transform, TransformSystem,
+};
+use bevy_rapier2d::prelude::PhysicsSet;
+
+/// Depth of an entity standing at y = 0, sorted entities stay between the map and
+/// the foreground layers for any y within a few thousand pixels of it.
+pub const Y_SORT_Z: f32 = 10.0;
+/// Depth change per pixel of height.
+pub const Y_SORT_SCALE: f32 = 0.001;
+
+pub struct YSortPlugin;
+
+impl Plugin for YSortPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            PostUpdate,
+            y_sort
+                .after(PhysicsSet::Writeback)
+                .before(TransformSystem::TransformPropagate),
+        );
+    }
+}
+
+/// Draws the entity in front of everything standing above it on screen and behind
+/// everything below it. Only for top level entities, the z is set in world space.
+#[derive(Component, Debug, Clone, Copy, Default)]
+pub struct YSort {
+    /// Height of the point the entity stands on, usually its feet, relative to the
+    /// transform's translation.
+    pub offset: f32,
+}
+
+impl YSort {
+    pub fn new(offset: f32) -> Self {
+        Self { offset }
+    }
+}
+
+pub fn y_sort_z(y: f32) -> f32 {
+    Y_SORT_Z - y * Y_SORT_SCALE
+}
+
+pub fn y_sort(mut query: Query<(&YSort, &mut Transform)>) {
+    for (y_sort, mut transform) in query.iter_mut() {
+        let z = y_sort_z(transform.translation.y + y_sort.offset);
+        // Avoids flagging every sorted transform as changed each frame.
+        if transform.translation.z != z {
+            transform.translation.z = z;
+        }
+    }
+}